@@ -0,0 +1,160 @@
+/// Worst errors of an approximation against a higher precision reference, together with the inputs they occur at.
+///
+/// Points where the reference is zero don't count towards the relative error.
+/// ```
+/// use tiny_lib::accuracy::*;
+/// let stats = ErrorStats::sweep(|a| a * (1.0 - a * a / 6.0), |a| a - a * a * a / 6.0, -1.0, 1.0, 1000);
+/// assert!(stats.max_ulp <= 2.0);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ErrorStats {
+	pub max_abs:f64,
+	pub max_abs_at:f32,
+	pub max_rel:f64,
+	pub max_rel_at:f32,
+	pub max_ulp:f64,
+	pub max_ulp_at:f32,
+}
+
+/// Distance between `reference` and the next `f32` away from zero, which is the unit in the last place of `f32`
+/// results near it.
+pub fn ulp_f32(reference:f64) -> f64 {
+	const MIN_EXPONENT:i32 = -126;
+	const MANTISSA_BITS:i32 = 23;
+	let exponent = ((reference.to_bits() >> 52) & 0x7FF) as i32 - 1023;
+	let exponent = if exponent < MIN_EXPONENT { MIN_EXPONENT } else { exponent };
+	return f64::from_bits(((exponent - MANTISSA_BITS + 1023) as u64) << 52);
+}
+
+/// Error of `value` against `reference`, in units in the last place of `f32`.
+pub fn ulp_error(value:f32, reference:f64) -> f64 {
+	let diff = value as f64 - reference;
+	return (if diff < 0.0 { -diff } else { diff }) / ulp_f32(reference);
+}
+
+/// Maps floats onto integers in the same order, so that neighbours differ by 1. Both zeros map to 0.
+fn ordered(a:f32) -> i32 {
+	let bits = a.to_bits() as i32;
+	return if bits < 0 { i32::MIN - bits } else { bits };
+}
+fn unordered(i:i32) -> f32 {
+	return f32::from_bits((if i < 0 { i32::MIN - i } else { i }) as u32);
+}
+
+/// Whether `error` replaces the `worst` one so far. NaN counts as the worst error, so once recorded it stays.
+#[inline(always)]
+fn is_worse(error:f64, worst:f64) -> bool {
+	return !worst.is_nan() && (error.is_nan() || error > worst);
+}
+
+impl ErrorStats {
+	/// Accounts one result `value` of input `a`, whose exact result is `reference`.
+	pub fn add(&mut self, a:f32, value:f32, reference:f64) {
+		let diff = value as f64 - reference;
+		let abs = if diff < 0.0 { -diff } else { diff };
+		if is_worse(abs, self.max_abs) {
+			self.max_abs = abs;
+			self.max_abs_at = a;
+		}
+		if reference != 0.0 {
+			let rel = abs / if reference < 0.0 { -reference } else { reference };
+			if is_worse(rel, self.max_rel) {
+				self.max_rel = rel;
+				self.max_rel_at = a;
+			}
+		}
+		let ulp = abs / ulp_f32(reference);
+		if is_worse(ulp, self.max_ulp) {
+			self.max_ulp = ulp;
+			self.max_ulp_at = a;
+		}
+	}
+
+	/// Combines the worst errors of two sweeps.
+	pub fn merge(mut self, other:ErrorStats) -> ErrorStats {
+		if is_worse(other.max_abs, self.max_abs) {
+			self.max_abs = other.max_abs;
+			self.max_abs_at = other.max_abs_at;
+		}
+		if is_worse(other.max_rel, self.max_rel) {
+			self.max_rel = other.max_rel;
+			self.max_rel_at = other.max_rel_at;
+		}
+		if is_worse(other.max_ulp, self.max_ulp) {
+			self.max_ulp = other.max_ulp;
+			self.max_ulp_at = other.max_ulp_at;
+		}
+		return self;
+	}
+
+	/// Errors at `steps + 1` evenly spaced points of [from, to].
+	pub fn sweep(mut f:impl FnMut(f32) -> f32, mut reference:impl FnMut(f64) -> f64, from:f32, to:f32, steps:u32) -> ErrorStats {
+		let mut ret = ErrorStats::default();
+		for i in 0..=steps {
+			let a = from + (to - from) * (i as f32 / steps as f32);
+			ret.add(a, f(a), reference(a as f64));
+		}
+		return ret;
+	}
+
+	/// Errors at every `stride`th float of [from, to], so a stride of 1 tests every input exhaustively.
+	pub fn sweep_floats(mut f:impl FnMut(f32) -> f32, mut reference:impl FnMut(f64) -> f64, from:f32, to:f32, stride:u32) -> ErrorStats {
+		let mut ret = ErrorStats::default();
+		let end = ordered(to) as i64;
+		let mut i = ordered(from) as i64;
+		while i <= end {
+			let a = unordered(i as i32);
+			ret.add(a, f(a), reference(a as f64));
+			i += stride as i64;
+		}
+		return ret;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ulps() {
+		assert_eq!(ulp_f32(1.0), f32::EPSILON as f64);
+		assert_eq!(ulp_f32(-1.5), f32::EPSILON as f64);
+		assert_eq!(ulp_f32(0.0), 2f64.powi(-149));
+		assert_eq!(ulp_f32(1e-40), 2f64.powi(-149));
+		assert_eq!(ulp_error(1.0 + f32::EPSILON, 1.0), 1.0);
+		assert_eq!(ulp_error(f32::from_bits(3), 0.0), 3.0);
+	}
+
+	#[test]
+	fn float_order() {
+		for a in [-f32::MAX, -1.0, -f32::from_bits(1), 0.0, f32::from_bits(1), 1.0, f32::MAX] {
+			assert_eq!(unordered(ordered(a)).to_bits(), a.to_bits());
+		}
+		assert_eq!(ordered(-0.0), 0);
+		assert_eq!(ordered(f32::from_bits(1)) - ordered(-f32::from_bits(1)), 2);
+		let mut count = 0;
+		let stats = ErrorStats::sweep_floats(|a| { count += 1; f32::from_bits(a.to_bits() + 1) }, |a| a, -1e-44, 1e-44, 1);
+		assert_eq!(count, 15); // 7 negative, 7 positive and zero
+		assert_eq!((stats.max_ulp, stats.max_rel), (1.0, 1.0));
+	}
+
+	#[test]
+	fn worst_points() {
+		let stats = ErrorStats::sweep(|a| a + 0.5 * a * a, |a| a, -1.0, 2.0, 3);
+		assert_eq!((stats.max_abs, stats.max_abs_at), (2.0, 2.0));
+		assert_eq!((stats.max_rel, stats.max_rel_at), (1.0, 2.0));
+		let other = ErrorStats::sweep(|a| a * 2.0, |a| a, 4.0, 4.0, 1);
+		assert_eq!(stats.merge(other).max_abs_at, 4.0);
+		assert_eq!(stats.merge(other).max_rel_at, 2.0);
+	}
+
+	#[test]
+	fn nan_errors_stick() {
+		let nan_at = |a:f32| a > 0.5 && a < 0.7; // only the point near 0.6, followed by exact ones
+		let stats = ErrorStats::sweep(|a| if nan_at(a) { f32::NAN } else { a }, |a| a, -1.0, 1.0, 10);
+		assert!(stats.max_abs.is_nan() && stats.max_rel.is_nan() && stats.max_ulp.is_nan());
+		assert!(nan_at(stats.max_abs_at) && nan_at(stats.max_rel_at) && nan_at(stats.max_ulp_at));
+		let finite = ErrorStats::sweep(|a| a * 2.0, |a| a, 1.0, 2.0, 1);
+		assert!(finite.merge(stats).max_abs.is_nan() && stats.merge(finite).max_abs.is_nan());
+	}
+}
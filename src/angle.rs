@@ -0,0 +1,301 @@
+// Angles tagged with their unit, so radians, degrees and turns can't be mixed up.
+//
+// A plain `f32` only converts into `Rad`, so functions taking `impl Into<Rad>` keep accepting radians
+// and a bare number never silently means degrees or turns.
+
+use core::ops::*;
+use crate::trig::*;
+use crate::math;
+
+/// Angle in radians.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Rad(pub f32);
+/// Angle in degrees.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Deg(pub f32);
+/// Angle in turns, 1.0 is a full turn.
+#[derive(Copy, Clone, Debug, Default, PartialEq, PartialOrd)]
+pub struct Turns(pub f32);
+
+/// Common interface of `Rad`, `Deg` and `Turns`.
+///
+/// Degrees and turns are reduced exactly, so their sine and cosine keep the max error of `sincos32` for
+/// any finite input.
+/// ```
+/// use tiny_lib::angle::*;
+/// assert_eq!(Deg(3600030.0).sin(), Deg(30.0).sin());
+/// assert_eq!(Turns(-0.75).wrap(), Turns(0.25));
+/// assert!((Rad::from(Deg(180.0)).0 - core::f32::consts::PI).abs() < 1e-6);
+/// ```
+pub trait Angle : Copy + Into<Rad> {
+	/// Same angle within [-HALF, HALF].
+	fn wrap(self) -> Self;
+	/// Same angle within [0, FULL).
+	fn normalize(self) -> Self;
+	/// Binary angle, where `u32` wraps around once per full turn, as used by `lookup` and `cordic`.
+	/// Non-finite angles give 0.
+	fn to_bam32(self) -> u32;
+	/// Sine and cosine, in the given `Precision` tier.
+	fn sincos_with<P:Precision>(self) -> (f32, f32);
+	/// Tangent, in the given `Precision` tier.
+	fn tan_with<P:Precision>(self) -> f32;
+
+	/// Sine, max error 2.6e-7.
+	#[inline(always)]
+	fn sin(self) -> f32 {
+		return self.sincos_with::<Precise>().0;
+	}
+	/// Cosine, max error 2.6e-7.
+	#[inline(always)]
+	fn cos(self) -> f32 {
+		return self.sincos_with::<Precise>().1;
+	}
+	/// Sine and cosine, max error 2.6e-7.
+	#[inline(always)]
+	fn sincos(self) -> (f32, f32) {
+		return self.sincos_with::<Precise>();
+	}
+	/// Tangent, max relative error 4e-7.
+	#[inline(always)]
+	fn tan(self) -> f32 {
+		return self.tan_with::<Precise>();
+	}
+}
+
+/// `|a|` modulo a whole `period`, exactly. The result is a multiple of the last place of `|a|` and at most
+/// `|a|`, so it's representable.
+#[inline(always)]
+fn remainder(abs:f32, period:u32) -> f32 {
+	if abs < 16777216.0 { // 2^24, from which on every f32 is whole
+		let whole = abs as u32;
+		return (whole % period) as f32 + (abs - whole as f32);
+	}
+	return (abs as u128 % period as u128) as f32;
+}
+
+macro_rules! impl_angle {
+	(
+		$($angle:ident; $full:expr; $bam_scale:expr);+
+	) => {
+		$(
+			impl $angle {
+				pub const ZERO:$angle = $angle(0.0);
+				pub const HALF:$angle = $angle($full * 0.5);
+				pub const FULL:$angle = $angle($full);
+				/// 2^32 / FULL, in f64 for the exact rounding of `to_bam32`.
+				const BAM_SCALE:f64 = $bam_scale;
+			}
+
+			impl Add for $angle { type Output = $angle; #[inline(always)] fn add(self, rhs:$angle) -> $angle { $angle(self.0 + rhs.0) } }
+			impl Sub for $angle { type Output = $angle; #[inline(always)] fn sub(self, rhs:$angle) -> $angle { $angle(self.0 - rhs.0) } }
+			impl Neg for $angle { type Output = $angle; #[inline(always)] fn neg(self) -> $angle { $angle(-self.0) } }
+			impl Mul<f32> for $angle { type Output = $angle; #[inline(always)] fn mul(self, rhs:f32) -> $angle { $angle(self.0 * rhs) } }
+			impl Mul<$angle> for f32 { type Output = $angle; #[inline(always)] fn mul(self, rhs:$angle) -> $angle { $angle(self * rhs.0) } }
+			impl Div<f32> for $angle { type Output = $angle; #[inline(always)] fn div(self, rhs:f32) -> $angle { $angle(self.0 / rhs) } }
+			impl Div for $angle { type Output = f32; #[inline(always)] fn div(self, rhs:$angle) -> f32 { self.0 / rhs.0 } }
+			impl AddAssign for $angle { #[inline(always)] fn add_assign(&mut self, rhs:$angle) { self.0 += rhs.0; } }
+			impl SubAssign for $angle { #[inline(always)] fn sub_assign(&mut self, rhs:$angle) { self.0 -= rhs.0; } }
+			impl MulAssign<f32> for $angle { #[inline(always)] fn mul_assign(&mut self, rhs:f32) { self.0 *= rhs; } }
+			impl DivAssign<f32> for $angle { #[inline(always)] fn div_assign(&mut self, rhs:f32) { self.0 /= rhs; } }
+		)+
+	};
+}
+
+impl From<f32> for Rad { #[inline(always)] fn from(a:f32) -> Rad { Rad(a) } }
+
+macro_rules! impl_angle_from {
+	(
+		$($to:ident <- $($from:ident),+);+
+	) => {
+		$($(
+			impl From<$from> for $to {
+				#[inline(always)]
+				fn from(a:$from) -> $to {
+					const SCALE:f32 = $to::FULL.0 / $from::FULL.0;
+					return $to(a.0 * SCALE);
+				}
+			}
+		)+)+
+	};
+}
+
+impl_angle! {
+	Rad; TAU; 4294967296.0 / core::f64::consts::TAU;
+	Deg; 360.0; 4294967296.0 / 360.0;
+	Turns; 1.0; 4294967296.0
+}
+
+impl_angle_from! {
+	Rad <- Deg, Turns;
+	Deg <- Rad, Turns;
+	Turns <- Rad, Deg
+}
+
+/// `Angle` of a unit whose full turn is a whole number, which allows the exact reduction.
+macro_rules! impl_angle_exact {
+	(
+		$($angle:ident; $period:expr);+
+	) => {
+		$(
+			impl Angle for $angle {
+				#[inline(always)]
+				fn wrap(self) -> $angle {
+					let abs = math::abs(self.0);
+					if !(abs <= f32::MAX) {
+						return $angle(f32::NAN);
+					}
+					let r = remainder(abs, $period);
+					let r = if r > Self::HALF.0 { r - Self::FULL.0 } else { r }; // exact, as r >= FULL/2
+					return $angle(if self.0 < 0.0 { -r } else { r });
+				}
+				#[inline(always)]
+				fn normalize(self) -> $angle {
+					let r = self.wrap().0;
+					if !(r < 0.0) {
+						return $angle(r + 0.0); // -0.0 to 0.0
+					}
+					let r = r + Self::FULL.0;
+					return $angle(if r < Self::FULL.0 { r } else { 0.0 }); // tiny negative angles round up to FULL
+				}
+				#[inline(always)]
+				fn to_bam32(self) -> u32 {
+					return (self.wrap().0 as f64 * Self::BAM_SCALE) as i64 as u32;
+				}
+				#[inline(always)]
+				fn sincos_with<P:Precision>(self) -> (f32, f32) {
+					if !(math::abs(self.0) <= f32::MAX) {
+						return (f32::NAN, f32::NAN);
+					}
+					return P::sincos_bam32(self.to_bam32());
+				}
+				#[inline(always)]
+				fn tan_with<P:Precision>(self) -> f32 {
+					return P::tan(Rad::from(self.wrap()).0);
+				}
+			}
+		)+
+	};
+}
+
+impl_angle_exact! {
+	Deg; 360;
+	Turns; 1
+}
+
+impl Angle for Rad {
+	#[inline(always)]
+	fn wrap(self) -> Rad {
+		// The quadrant of the reduction is exact, only adding it back to the remainder rounds.
+		let (quad, r) = reduce_pi_half(math::abs(self.0));
+		let quad = if quad == 2 && r > 0.0 { -2.0 } else if quad == 3 { -1.0 } else { quad as f32 };
+		let ret = quad * PI_HALF + (quad * PI_HALF_LO + r);
+		return Rad(if self.0 < 0.0 { -ret } else { ret });
+	}
+	#[inline(always)]
+	fn normalize(self) -> Rad {
+		let r = self.wrap().0;
+		if !(r < 0.0) {
+			return Rad(r + 0.0); // -0.0 to 0.0
+		}
+		let r = r + TAU;
+		return Rad(if r < TAU { r } else { 0.0 }); // tiny negative angles round up to TAU
+	}
+	#[inline(always)]
+	fn to_bam32(self) -> u32 {
+		return (self.wrap().0 as f64 * Self::BAM_SCALE) as i64 as u32;
+	}
+	#[inline(always)]
+	fn sincos_with<P:Precision>(self) -> (f32, f32) {
+		return P::sincos(self.0);
+	}
+	#[inline(always)]
+	fn tan_with<P:Precision>(self) -> f32 {
+		return P::tan(self.0);
+	}
+}
+
+impl Rad {
+	/// Arctangent, max error 2.3e-7.
+	#[inline(always)]
+	pub fn atan(a:f32) -> Rad {
+		return Rad(atan32(a));
+	}
+	/// Angle of the vector (x, y), within [-PI, PI]. Max error 3.1e-7.
+	#[inline(always)]
+	pub fn atan2(y:f32, x:f32) -> Rad {
+		return Rad(atan2_32(y, x));
+	}
+	/// Arcsine within [-1, 1], max error 4.4e-7.
+	#[inline(always)]
+	pub fn asin(a:f32) -> Rad {
+		return Rad(asin32(a));
+	}
+	/// Arccosine within [-1, 1], max error 4.4e-7.
+	#[inline(always)]
+	pub fn acos(a:f32) -> Rad {
+		return Rad(acos32(a));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::accuracy::ErrorStats;
+
+	#[test]
+	fn conversions() {
+		assert_eq!(Deg::from(Turns(0.25)), Deg(90.0));
+		assert_eq!(Turns::from(Deg(-180.0)), Turns(-0.5));
+		assert_eq!(Rad::from(Turns(0.5)), Rad(PI));
+		assert!((Deg::from(Rad(1.0)).0 - 57.29578).abs() < 1e-5);
+		assert!((Turns::from(Rad(PI_HALF)).0 - 0.25).abs() < 1e-7);
+		assert_eq!(Rad::from(2.0), Rad(2.0));
+		assert_eq!(Deg(30.0) + Deg(60.0) * 2.0 - Deg(10.0), Deg(140.0));
+		assert_eq!((2.0 * -Turns(0.25), Turns(1.0) / Turns(0.25)), (Turns(-0.5), 4.0));
+		let mut a = Rad(1.0);
+		a += Rad(1.0);
+		a /= 4.0;
+		assert_eq!(a, Rad(0.5));
+	}
+
+	#[test]
+	fn wrapping() {
+		assert_eq!(Deg(540.0).wrap(), Deg(180.0));
+		assert_eq!(Deg(-190.0).wrap(), Deg(170.0));
+		assert_eq!(Deg(-190.0).normalize(), Deg(170.0));
+		assert_eq!(Deg(-10.0).normalize(), Deg(350.0));
+		assert_eq!(Deg(1e30).wrap(), Deg(((1e30f32 as u128) % 360) as f32));
+		assert_eq!(Deg(-1e-10).normalize(), Deg(0.0));
+		assert_eq!(Turns(3.75).wrap(), Turns(-0.25));
+		assert_eq!(Turns(-3.75).normalize(), Turns(0.25));
+		assert_eq!(Turns(-0.0).normalize().0.to_bits(), 0);
+		assert!(Deg(f32::INFINITY).wrap().0.is_nan() && Rad(f32::NAN).normalize().0.is_nan());
+		assert_eq!((Deg(90.0).to_bam32(), Turns(-0.25).to_bam32()), (1 << 30, 3 << 30));
+		assert!((Rad(-PI_HALF).to_bam32().wrapping_sub(3 << 30) as i32).abs() < 128);
+		for i in -1000..=1000 {
+			let a = i as f32 * 0.37;
+			let wrapped = Rad(a).wrap().0;
+			let expected = (a as f64 + core::f64::consts::PI).rem_euclid(core::f64::consts::TAU) - core::f64::consts::PI;
+			assert!(wrapped.abs() <= PI && (wrapped as f64 - expected).abs() < 5e-7, "{} {}", a, wrapped);
+			let normalized = Rad(a).normalize().0;
+			assert!(normalized >= 0.0 && normalized < TAU, "{} {}", a, normalized);
+		}
+	}
+
+	#[test]
+	fn exact_reduction_error_bounds() {
+		let deg = |a:f64| ((a % 360.0) * (core::f64::consts::PI / 180.0)).sin();
+		let sin = ErrorStats::sweep(|a| Deg(a).sin(), deg, -1e6, 1e6, 1_000_000).max_abs;
+		assert!(sin <= 2.6e-7, "{}", sin);
+		let turns = |a:f64| ((a % 1.0) * core::f64::consts::TAU).cos();
+		let cos = ErrorStats::sweep(|a| Turns(a).cos(), turns, -1e4, 1e4, 1_000_000).max_abs;
+		assert!(cos <= 2.6e-7, "{}", cos);
+		assert!((Deg(-90.0).sin() + 1.0).abs() <= 2.6e-7 && (Turns(1e20).cos() - 1.0).abs() <= 2.6e-7);
+		assert_eq!(Deg(3600030.0).sin(), Deg(30.0).sin());
+		assert!((Deg(45.0).tan() - 1.0).abs() < 4e-7 && (Deg(3600045.0).tan() - 1.0).abs() < 4e-7);
+		assert_eq!(Rad(1.0).sincos(), sincos32(1.0));
+		assert_eq!(Rad(1.0).sincos_with::<Fast>(), Fast::sincos(1.0));
+		assert!(Deg(f32::NAN).sin().is_nan() && Turns(f32::INFINITY).cos().is_nan());
+		assert_eq!(Rad::atan2(1.0, 0.0), Rad(PI_HALF));
+	}
+}
@@ -0,0 +1,292 @@
+// Trig over slices. Chunks, whose arguments all take the Cody-Waite reduction, are evaluated without branches,
+// which the compiler vectorises. Other chunks and the tail fall back to the scalar functions. Every step is the
+// same IEEE operation as in the scalar code, so results are identical to `cos32` and `sincos32` for every input.
+//
+// With the `simd` feature, x86 targets compiled with SSE2 or AVX2 use `core::arch` intrinsics instead.
+
+use core::convert::TryInto;
+use core::f32::consts::FRAC_2_PI;
+use crate::trig::*;
+use crate::math;
+
+const LANES:usize = 8;
+
+#[inline(always)]
+fn in_range(a:&[f32; LANES]) -> bool {
+	let mut ret = true;
+	for &a in a {
+		ret &= math::abs(a) <= CODY_WAITE_MAX; // NaN fails too
+	}
+	return ret;
+}
+
+/// Sine and cosine of `|a|` for `|a| <= CODY_WAITE_MAX`, doing what `reduce_pi_half` and
+/// `Precision::sincos_quadrant` do, with selects instead of branches.
+#[inline(always)]
+fn sincos_abs(a:f32) -> (f32, f32) {
+	let abs = math::abs(a);
+	let n = (abs * FRAC_2_PI + 0.5) as i32;
+	let nf = n as f32;
+	let r = ((abs - nf * CODY_WAITE[0]) - nf * CODY_WAITE[1]) - nf * CODY_WAITE[2];
+	let s = sin_minimax4(r);
+	let c = cos_minimax5(r);
+	let (x, y) = if n & 1 != 0 { (c, -s) } else { (s, c) };
+	let negate = n & 2 != 0;
+	return (if negate { -x } else { x }, if negate { -y } else { y });
+}
+
+/// Writes `cos32` of every element of `a` into `out`. Panics if the lengths differ.
+pub fn cos_slice(a:&[f32], out:&mut [f32]) {
+	assert_eq!(a.len(), out.len(), "input and output lengths differ");
+	let done = arch::cos_chunks(a, out);
+	let (a, out) = (&a[done..], &mut out[done..]);
+
+	let mut out_chunks = out.chunks_exact_mut(LANES);
+	let mut a_chunks = a.chunks_exact(LANES);
+	for (a, out) in (&mut a_chunks).zip(&mut out_chunks) {
+		let a:&[f32; LANES] = a.try_into().unwrap();
+		let out:&mut [f32; LANES] = out.try_into().unwrap();
+		if in_range(a) {
+			for i in 0..LANES {
+				out[i] = sincos_abs(a[i]).1;
+			}
+		} else {
+			for i in 0..LANES {
+				out[i] = cos32(a[i]);
+			}
+		}
+	}
+	for (a, out) in a_chunks.remainder().iter().zip(out_chunks.into_remainder()) {
+		*out = cos32(*a);
+	}
+}
+
+/// Writes `sincos32` of every element of `a` into `sin` and `cos`. Panics if the lengths differ.
+pub fn sincos_slice(a:&[f32], sin:&mut [f32], cos:&mut [f32]) {
+	assert!(a.len() == sin.len() && a.len() == cos.len(), "input and output lengths differ");
+	let done = arch::sincos_chunks(a, sin, cos);
+	let (a, sin, cos) = (&a[done..], &mut sin[done..], &mut cos[done..]);
+
+	let mut sin_chunks = sin.chunks_exact_mut(LANES);
+	let mut cos_chunks = cos.chunks_exact_mut(LANES);
+	let mut a_chunks = a.chunks_exact(LANES);
+	for ((a, sin), cos) in (&mut a_chunks).zip(&mut sin_chunks).zip(&mut cos_chunks) {
+		let a:&[f32; LANES] = a.try_into().unwrap();
+		let sin:&mut [f32; LANES] = sin.try_into().unwrap();
+		let cos:&mut [f32; LANES] = cos.try_into().unwrap();
+		if in_range(a) {
+			for i in 0..LANES {
+				let (s, c) = sincos_abs(a[i]);
+				sin[i] = if a[i].is_sign_negative() { -s } else { s }; // sin(-x) = -sin(x)
+				cos[i] = c;
+			}
+		} else {
+			for i in 0..LANES {
+				(sin[i], cos[i]) = sincos32(a[i]);
+			}
+		}
+	}
+	let remainder = a_chunks.remainder().iter().zip(sin_chunks.into_remainder()).zip(cos_chunks.into_remainder());
+	for ((a, sin), cos) in remainder {
+		(*sin, *cos) = sincos32(*a);
+	}
+}
+
+#[cfg(not(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), any(target_feature = "sse2", target_feature = "avx2"))))]
+mod arch {
+	#[inline(always)]
+	pub fn cos_chunks(_:&[f32], _:&mut [f32]) -> usize { 0 }
+	#[inline(always)]
+	pub fn sincos_chunks(_:&[f32], _:&mut [f32], _:&mut [f32]) -> usize { 0 }
+}
+
+/// The same operations as `sincos_abs`, on SSE2 or AVX2 registers. Returns how many leading elements were done.
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), any(target_feature = "sse2", target_feature = "avx2")))]
+mod arch {
+	#[cfg(target_arch = "x86")]
+	use core::arch::x86::*;
+	#[cfg(target_arch = "x86_64")]
+	use core::arch::x86_64::*;
+	use core::f32::consts::FRAC_2_PI;
+	use crate::trig::*;
+
+	#[cfg(target_feature = "avx2")]
+	mod ops {
+		use super::*;
+		pub type F = __m256;
+		pub const WIDTH:usize = 8;
+		pub const ALL:i32 = 0xFF;
+		#[inline(always)] pub unsafe fn load(p:*const f32) -> F { _mm256_loadu_ps(p) }
+		#[inline(always)] pub unsafe fn store(p:*mut f32, a:F) { _mm256_storeu_ps(p, a) }
+		#[inline(always)] pub unsafe fn splat(a:f32) -> F { _mm256_set1_ps(a) }
+		#[inline(always)] pub unsafe fn add(a:F, b:F) -> F { _mm256_add_ps(a, b) }
+		#[inline(always)] pub unsafe fn sub(a:F, b:F) -> F { _mm256_sub_ps(a, b) }
+		#[inline(always)] pub unsafe fn mul(a:F, b:F) -> F { _mm256_mul_ps(a, b) }
+		#[inline(always)] pub unsafe fn and(a:F, b:F) -> F { _mm256_and_ps(a, b) }
+		#[inline(always)] pub unsafe fn xor(a:F, b:F) -> F { _mm256_xor_ps(a, b) }
+		#[inline(always)] pub unsafe fn select(mask:F, a:F, b:F) -> F { _mm256_blendv_ps(b, a, mask) }
+		#[inline(always)] pub unsafe fn le(a:F, b:F) -> F { _mm256_cmp_ps::<_CMP_LE_OQ>(a, b) }
+		#[inline(always)] pub unsafe fn mask(a:F) -> i32 { _mm256_movemask_ps(a) }
+		/// Truncates to integers, returning them as floats and masks of bits 1 and 2 being set.
+		#[inline(always)] pub unsafe fn trunc_bits(a:F) -> (F, F, F) {
+			let n = _mm256_cvttps_epi32(a);
+			let one = _mm256_set1_epi32(1);
+			let two = _mm256_set1_epi32(2);
+			let odd = _mm256_cmpeq_epi32(_mm256_and_si256(n, one), one);
+			let negate = _mm256_cmpeq_epi32(_mm256_and_si256(n, two), two);
+			return (_mm256_cvtepi32_ps(n), _mm256_castsi256_ps(odd), _mm256_castsi256_ps(negate));
+		}
+	}
+	#[cfg(not(target_feature = "avx2"))]
+	mod ops {
+		use super::*;
+		pub type F = __m128;
+		pub const WIDTH:usize = 4;
+		pub const ALL:i32 = 0xF;
+		#[inline(always)] pub unsafe fn load(p:*const f32) -> F { _mm_loadu_ps(p) }
+		#[inline(always)] pub unsafe fn store(p:*mut f32, a:F) { _mm_storeu_ps(p, a) }
+		#[inline(always)] pub unsafe fn splat(a:f32) -> F { _mm_set1_ps(a) }
+		#[inline(always)] pub unsafe fn add(a:F, b:F) -> F { _mm_add_ps(a, b) }
+		#[inline(always)] pub unsafe fn sub(a:F, b:F) -> F { _mm_sub_ps(a, b) }
+		#[inline(always)] pub unsafe fn mul(a:F, b:F) -> F { _mm_mul_ps(a, b) }
+		#[inline(always)] pub unsafe fn and(a:F, b:F) -> F { _mm_and_ps(a, b) }
+		#[inline(always)] pub unsafe fn xor(a:F, b:F) -> F { _mm_xor_ps(a, b) }
+		#[inline(always)] pub unsafe fn select(mask:F, a:F, b:F) -> F { _mm_or_ps(_mm_and_ps(mask, a), _mm_andnot_ps(mask, b)) }
+		#[inline(always)] pub unsafe fn le(a:F, b:F) -> F { _mm_cmple_ps(a, b) }
+		#[inline(always)] pub unsafe fn mask(a:F) -> i32 { _mm_movemask_ps(a) }
+		/// Truncates to integers, returning them as floats and masks of bits 1 and 2 being set.
+		#[inline(always)] pub unsafe fn trunc_bits(a:F) -> (F, F, F) {
+			let n = _mm_cvttps_epi32(a);
+			let one = _mm_set1_epi32(1);
+			let two = _mm_set1_epi32(2);
+			let odd = _mm_cmpeq_epi32(_mm_and_si128(n, one), one);
+			let negate = _mm_cmpeq_epi32(_mm_and_si128(n, two), two);
+			return (_mm_cvtepi32_ps(n), _mm_castsi128_ps(odd), _mm_castsi128_ps(negate));
+		}
+	}
+	use ops::*;
+
+	#[inline(always)]
+	unsafe fn cos_minimax5_v(a:F) -> F {
+		let a2 = mul(a, a);
+		let p = add(splat(COS_MINIMAX5[2]), mul(a2, splat(COS_MINIMAX5[3])));
+		let p = add(splat(COS_MINIMAX5[1]), mul(a2, p));
+		let p = add(splat(COS_MINIMAX5[0]), mul(a2, p));
+		return add(splat(1.0), mul(a2, p));
+	}
+
+	#[inline(always)]
+	unsafe fn sin_minimax4_v(a:F) -> F {
+		let a2 = mul(a, a);
+		let p = add(splat(SIN_MINIMAX4[1]), mul(a2, splat(SIN_MINIMAX4[2])));
+		let p = add(splat(SIN_MINIMAX4[0]), mul(a2, p));
+		return add(a, mul(mul(a, a2), p));
+	}
+
+	/// Sine and cosine of `|a|`, or `None` if a lane needs the Payne-Hanek reduction.
+	#[inline(always)]
+	unsafe fn sincos_abs(a:F) -> Option<(F, F)> {
+		let sign = splat(-0.0);
+		let abs = xor(a, and(a, sign));
+		if mask(le(abs, splat(CODY_WAITE_MAX))) != ALL {
+			return None;
+		}
+		let (nf, odd, negate) = trunc_bits(add(mul(abs, splat(FRAC_2_PI)), splat(0.5)));
+		let r = sub(abs, mul(nf, splat(CODY_WAITE[0])));
+		let r = sub(r, mul(nf, splat(CODY_WAITE[1])));
+		let r = sub(r, mul(nf, splat(CODY_WAITE[2])));
+		let s = sin_minimax4_v(r);
+		let c = cos_minimax5_v(r);
+		let x = select(odd, c, s);
+		let y = select(odd, xor(s, sign), c);
+		let negate = and(negate, sign);
+		return Some((xor(x, negate), xor(y, negate)));
+	}
+
+	pub fn cos_chunks(a:&[f32], out:&mut [f32]) -> usize {
+		let done = a.len() / WIDTH * WIDTH;
+		for i in (0..done).step_by(WIDTH) {
+			// Safety: i + WIDTH <= done <= a.len() == out.len(), and the intrinsics are enabled for the target.
+			unsafe {
+				match sincos_abs(load(a.as_ptr().add(i))) {
+					Some((_, c)) => store(out.as_mut_ptr().add(i), c),
+					None => for j in i..i + WIDTH {
+						out[j] = cos32(a[j]);
+					},
+				}
+			}
+		}
+		return done;
+	}
+
+	pub fn sincos_chunks(a:&[f32], sin:&mut [f32], cos:&mut [f32]) -> usize {
+		let done = a.len() / WIDTH * WIDTH;
+		for i in (0..done).step_by(WIDTH) {
+			// Safety: i + WIDTH <= done <= a.len() == sin.len() == cos.len(), and the intrinsics are enabled.
+			unsafe {
+				let v = load(a.as_ptr().add(i));
+				match sincos_abs(v) {
+					Some((s, c)) => {
+						store(sin.as_mut_ptr().add(i), xor(s, and(v, splat(-0.0)))); // sin(-x) = -sin(x)
+						store(cos.as_mut_ptr().add(i), c);
+					},
+					None => for j in i..i + WIDTH {
+						(sin[j], cos[j]) = sincos32(a[j]);
+					},
+				}
+			}
+		}
+		return done;
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::prng::MSWS;
+
+	fn inputs() -> [f32; 1003] {
+		let mut rng = MSWS::new(7);
+		let mut ret = [0f32; 1003];
+		for (i, a) in ret.iter_mut().enumerate() {
+			*a = match i % 5 {
+				0 => (rng.get_f32() - 0.5) * 20.0,
+				1 => (rng.get_f32() - 0.5) * 2.0 * CODY_WAITE_MAX,
+				2 => f32::from_bits(rng.get_u32()), // any float, including NaN, infinities and huge values
+				_ => (rng.get_f32() - 0.5) * 1000.0,
+			};
+		}
+		ret[0] = -0.0;
+		ret[1] = CODY_WAITE_MAX;
+		ret[2] = -CODY_WAITE_MAX;
+		return ret;
+	}
+
+	fn same(a:f32, b:f32) -> bool {
+		return a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan());
+	}
+
+	#[test]
+	fn identical_to_scalar() {
+		let a = inputs();
+		for len in [0, 1, 7, 8, 9, 64, 1003] {
+			let a = &a[..len];
+			let mut out = [0f32; 1003];
+			let mut sin = [0f32; 1003];
+			let mut cos = [0f32; 1003];
+			cos_slice(a, &mut out[..len]);
+			sincos_slice(a, &mut sin[..len], &mut cos[..len]);
+			for i in 0..len {
+				let (s, c) = sincos32(a[i]);
+				assert!(same(out[i], cos32(a[i])), "{} {} {}", a[i], out[i], cos32(a[i]));
+				assert!(same(sin[i], s) && same(cos[i], c), "{} {} {}", a[i], sin[i], s);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic]
+	fn length_mismatch() {
+		cos_slice(&[1.0, 2.0], &mut [0.0]);
+	}
+}
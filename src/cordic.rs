@@ -0,0 +1,164 @@
+/// Number of CORDIC iterations, after which `atan(2^-i)` is below one binary angle unit.
+const ITERATIONS:usize = 30;
+
+/// `atan(2^-i)` as a binary angle, where `u32` wraps around once per full turn.
+const ATAN_BAM:[u32; ITERATIONS] = [
+	536870912, 316933406, 167458907, 85004756, 42667331, 21354465, 10679838, 5340245,
+	2670163, 1335087, 667544, 333772, 166886, 83443, 41722, 20861,
+	10430, 5215, 2608, 1304, 652, 326, 163, 81,
+	41, 20, 10, 5, 3, 1,
+];
+
+/// Inverse of the CORDIC gain `prod(sqrt(1 + 2^-2i))`, in Q2.30 and Q0.32.
+const GAIN_INV_Q30:i32 = 652032874;
+const GAIN_INV_Q32:u128 = 2608131496;
+
+/// Half a turn as a binary angle.
+const BAM_HALF:u32 = 1 << 31;
+
+/// Sine and cosine of a binary angle in Q2.30, by CORDIC rotation mode.
+///
+/// Source: J. E. Volder, "The CORDIC Trigonometric Computing Technique"
+fn rotate(a:u32) -> (i32, i32) {
+	// CORDIC converges within [-PI/2, PI/2], the other half is the negated result of the opposite angle.
+	let mut z = a as i32;
+	let flip = !(-(1 << 30)..=(1 << 30)).contains(&z);
+	if flip {
+		z = z.wrapping_add(BAM_HALF as i32);
+	}
+	let mut x = GAIN_INV_Q30;
+	let mut y = 0i32;
+	for (i, &atan) in ATAN_BAM.iter().enumerate() {
+		let (dx, dy) = (y >> i, x >> i);
+		if z >= 0 {
+			x -= dx;
+			y += dy;
+			z = z.wrapping_sub(atan as i32);
+		} else {
+			x += dx;
+			y -= dy;
+			z = z.wrapping_add(atan as i32);
+		}
+	}
+	return if flip { (-y, -x) } else { (y, x) };
+}
+
+/// Angle and magnitude of `(x, y)` in CORDIC vectoring mode. The magnitude carries the gain and 28 extra bits.
+fn vector(x:i32, y:i32) -> (u32, u64) {
+	let (mut x, mut y) = (x as i64, y as i64);
+	let mut z = 0u32;
+	// CORDIC converges in the right half plane, the left one is rotated by half a turn first.
+	if x < 0 {
+		x = -x;
+		y = -y;
+		z = BAM_HALF;
+	}
+	// |x|, |y| <= 2^31, which leaves room for the gain of 1.65 and the growth of sqrt(2) below 2^63.
+	x <<= 28;
+	y <<= 28;
+	for (i, &atan) in ATAN_BAM.iter().enumerate() {
+		let (dx, dy) = (y >> i, x >> i);
+		if y < 0 {
+			x -= dx;
+			y += dy;
+			z = z.wrapping_sub(atan);
+		} else {
+			x += dx;
+			y -= dy;
+			z = z.wrapping_add(atan);
+		}
+	}
+	return (z, x as u64);
+}
+
+/// Sine and cosine of a binary angle, where `u32` wraps around once per full turn, in Q1.15.
+/// Max error is 1 in the last place, 1.0 saturates to 32767.
+pub fn cordic_sincos_q15(a:u32) -> (i16, i16) {
+	let (s, c) = rotate(a);
+	let to_q15 = |v:i32| ((v + (1 << 14)) >> 15).min(i16::MAX as i32) as i16;
+	return (to_q15(s), to_q15(c));
+}
+
+/// Sine and cosine of a binary angle, where `u32` wraps around once per full turn, in Q16.16.
+/// Max error is 1 in the last place.
+pub fn cordic_sincos_q16(a:u32) -> (i32, i32) {
+	let (s, c) = rotate(a);
+	return ((s + (1 << 13)) >> 14, (c + (1 << 13)) >> 14);
+}
+
+/// Angle of `(x, y)` as a binary angle, where `u32` wraps around once per full turn, and its magnitude.
+/// `x` and `y` may be in any fixed-point format, as long as it's the same one, like Q1.15 or Q16.16.
+/// The magnitude is in that format too, unsigned, since it can exceed `i32::MAX`.
+///
+/// Max error is 2e-8 of a turn for the angle and 1 in the last place plus 1e-8 relative for the magnitude.
+/// The angle of `(0, 0)` is 0.
+pub fn cordic_polar(x:i32, y:i32) -> (u32, u32) {
+	if x == 0 && y == 0 {
+		return (0, 0);
+	}
+	let (angle, magnitude) = vector(x, y);
+	let magnitude = (magnitude as u128 * GAIN_INV_Q32 + (1 << 59)) >> 60;
+	return (angle, magnitude as u32);
+}
+
+/// Angle of `(x, y)` as a binary angle, where `u32` wraps around once per full turn.
+/// See `cordic_polar` for accepted formats and errors.
+pub fn cordic_atan2(y:i32, x:i32) -> u32 {
+	return cordic_polar(x, y).0;
+}
+
+/// Magnitude of `(x, y)`. See `cordic_polar` for accepted formats and errors.
+pub fn cordic_magnitude(x:i32, y:i32) -> u32 {
+	return cordic_polar(x, y).1;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const TURN:f64 = 4294967296.0;
+
+	fn bam_to_rad(a:u32) -> f64 {
+		return a as f64 * (core::f64::consts::TAU / TURN);
+	}
+
+	#[test]
+	fn rotation_error_bounds() {
+		for i in 0..=u16::MAX as u32 {
+			let a = i << 16 | i.wrapping_mul(40503) & 0xFFFF;
+			let (sin, cos) = (bam_to_rad(a).sin(), bam_to_rad(a).cos());
+			let (s15, c15) = cordic_sincos_q15(a);
+			assert!((s15 as f64 - sin * 32768.0).abs() <= 1.0, "{} {}", a, s15);
+			assert!((c15 as f64 - cos * 32768.0).abs() <= 1.0, "{} {}", a, c15);
+			let (s16, c16) = cordic_sincos_q16(a);
+			assert!((s16 as f64 - sin * 65536.0).abs() <= 1.0, "{} {}", a, s16);
+			assert!((c16 as f64 - cos * 65536.0).abs() <= 1.0, "{} {}", a, c16);
+		}
+		assert_eq!(cordic_sincos_q15(0), (0, 32767));
+		assert_eq!(cordic_sincos_q16(1 << 30), (65536, 0));
+	}
+
+	#[test]
+	fn vectoring_error_bounds() {
+		let mut state = 0x9E3779B97F4A7C15u64;
+		for i in 0..100_000 {
+			state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+			let shift = i % 32; // all magnitudes, down to single bits
+			let x = ((state >> 32) as i32) >> shift;
+			let y = (state as i32) >> shift;
+			let (angle, magnitude) = cordic_polar(x, y);
+			let (xf, yf) = (x as f64, y as f64);
+			if x != 0 || y != 0 {
+				let expected = (yf.atan2(xf) / core::f64::consts::TAU * TURN) as i64 as u32;
+				let err = angle.wrapping_sub(expected) as i32;
+				assert!(err.unsigned_abs() as f64 <= 2e-8 * TURN, "{} {} {}", x, y, err);
+			}
+			let length = (xf * xf + yf * yf).sqrt();
+			assert!((magnitude as f64 - length).abs() <= 1.0 + length * 1e-8, "{} {} {}", x, y, magnitude);
+		}
+		assert_eq!(cordic_atan2(0, 0), 0);
+		assert!((cordic_atan2(0, -5).wrapping_sub(BAM_HALF) as i32).abs() <= 16);
+		assert_eq!(cordic_magnitude(i32::MIN, i32::MIN), 3037000500);
+		assert_eq!(cordic_magnitude(-3 << 15, 4 << 15), 5 << 15);
+	}
+}
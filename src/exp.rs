@@ -0,0 +1,233 @@
+// Exponential and logarithm approximations.
+//
+// Kernels are minimax polynomials (relative error) for the reduced range, named after the number of
+// correct decimal digits, like the cosine kernels in `trig`. The exponent part is handled through the float bits.
+
+use core::f32::consts::{LN_2, LOG2_E, SQRT_2};
+
+/// 2^a, max relative error 8.6e-5 (4.1 decimal digits) only within [0, 1]. Exact at 0.
+pub const fn exp2_41s(a:f32) -> f32 {
+	const C1:f32 = 0.6951171663107927;
+	const C2:f32 = 0.2276433098166355;
+	const C3:f32 = 0.07706858485431084;
+
+	return 1.0 + a * (C1 + a * (C2 + a * C3));
+}
+/// 2^a, max relative error 8.3e-8 (7.1 decimal digits) only within [0, 1]. Exact at 0.
+pub const fn exp2_71s(a:f32) -> f32 {
+	const C1:f32 = 0.693151312359912;
+	const C2:f32 = 0.24016444344140586;
+	const C3:f32 = 0.055799937496408554;
+	const C4:f32 = 0.009016996280429256;
+	const C5:f32 = 0.0018671461245193938;
+
+	return 1.0 + a * (C1 + a * (C2 + a * (C3 + a * (C4 + a * C5))));
+}
+/// log2((1 + t) / (1 - t)), max relative error 2.3e-5 (4.6 decimal digits) only within [-(3 - 2*sqrt(2)), 3 - 2*sqrt(2)].
+pub const fn log2_46s(t:f32) -> f32 {
+	const C1:f32 = 2.8853258381885856;
+	const C3:f32 = 0.9791317087283119;
+
+	let t2 = t*t;
+
+	return t * (C1 + t2 * C3);
+}
+/// log2((1 + t) / (1 - t)), max relative error 7e-10 (9.1 decimal digits) only within [-(3 - 2*sqrt(2)), 3 - 2*sqrt(2)].
+pub const fn log2_91s(t:f32) -> f32 {
+	const C1:f32 = 2.8853900797884986;
+	const C3:f32 = 0.9617988479635851;
+	const C5:f32 = 0.576714347546349;
+	const C7:f32 = 0.4317368743550886;
+
+	let t2 = t*t;
+
+	return t * (C1 + t2 * (C3 + t2 * (C5 + t2 * C7)));
+}
+
+/// a * 2^n, for n within [-190, 127].
+#[inline(always)]
+fn scale2(a:f32, n:i32) -> f32 {
+	if n < -126 { // 2^n is subnormal, so scale in two steps, of which only the last one rounds
+		return a * f32::from_bits(((n + 64 + 127) as u32) << 23) * f32::from_bits(((-64 + 127) as u32) << 23);
+	}
+	return a * f32::from_bits(((n + 127) as u32) << 23);
+}
+/// 2^a = 2^floor(a) * 2^fract(a). Results below `f32::MIN_POSITIVE` are subnormal, as with `f32::exp2`.
+#[inline(always)]
+fn exp2_with(a:f32, kernel:fn(f32) -> f32) -> f32 {
+	if !(a < 128.0) {
+		return if a.is_nan() { a } else { f32::INFINITY };
+	}
+	if a < -150.0 {
+		return 0.0;
+	}
+	let i = a as i32;
+	let i = if (i as f32) > a { i - 1 } else { i }; // floor
+	return scale2(kernel(a - i as f32), i);
+}
+/// e^a = 2^n * e^r, where r = a - n*ln(2) is computed in two parts to keep it exact.
+#[inline(always)]
+fn exp_with(a:f32, kernel:fn(f32) -> f32) -> f32 {
+	const LN_2_HI:f32 = 0.693145751953125; // Low 9 bits are 0, so `n * LN_2_HI` is exact for |n| < 2^9.
+	const LN_2_LO:f32 = 1.4286068e-6;
+
+	if !(a < 88.8) {
+		return if a.is_nan() { a } else { f32::INFINITY };
+	}
+	if a < -104.0 { // Just below ln(2^-150), under which everything rounds to 0.
+		return 0.0;
+	}
+	let n = a * LOG2_E;
+	let n = if n < 0.0 && (n as i32) as f32 != n { n as i32 - 1 } else { n as i32 }; // floor
+	let r = (a - n as f32 * LN_2_HI) - n as f32 * LN_2_LO;
+	return scale2(exp2_with(r * LOG2_E, kernel), n);
+}
+/// log2(a) = exponent + log2(mantissa), with the mantissa within [sqrt(2)/2, sqrt(2)].
+#[inline(always)]
+fn log2_with(a:f32, kernel:fn(f32) -> f32) -> f32 {
+	if !(a > 0.0) {
+		return if a == 0.0 { f32::NEG_INFINITY } else { f32::NAN };
+	}
+	if a == f32::INFINITY {
+		return a;
+	}
+	let mut bits = a.to_bits();
+	let mut e = -127;
+	if bits < 0x00800000 { // subnormal
+		bits = (a * (1u32 << 23) as f32).to_bits();
+		e -= 23;
+	}
+	e += (bits >> 23) as i32;
+	let mut m = f32::from_bits((bits & 0x007FFFFF) | 0x3F800000);
+	if m > SQRT_2 {
+		m *= 0.5;
+		e += 1;
+	}
+	return e as f32 + kernel((m - 1.0) / (m + 1.0));
+}
+/// a^b through 2^(b*log2(a)). Negative `a` is only defined for integer `b`.
+#[inline(always)]
+fn pow_with(a:f32, b:f32, exp2_kernel:fn(f32) -> f32, log2_kernel:fn(f32) -> f32) -> f32 {
+	if b == 0.0 || a == 1.0 {
+		return 1.0;
+	}
+	if a.is_sign_negative() { // Also -0.0, which keeps its sign for odd `b`.
+		const INTEGER_ONLY:f32 = (1u32 << 24) as f32; // Every f32 above is an even integer.
+		let odd = if b < INTEGER_ONLY && b > -INTEGER_ONLY {
+			if (b as i32) as f32 != b {
+				if a != 0.0 {
+					return f32::NAN;
+				}
+				false
+			} else {
+				(b as i32) & 1 != 0
+			}
+		} else {
+			false
+		};
+		let ret = exp2_with(b * log2_with(-a, log2_kernel), exp2_kernel);
+		return if odd { -ret } else { ret };
+	}
+	return exp2_with(b * log2_with(a, log2_kernel), exp2_kernel);
+}
+
+/// 2^a, max relative error 8.6e-5.
+pub fn exp2_32_fast(a:f32) -> f32 { exp2_with(a, exp2_41s) }
+/// 2^a, max relative error 2e-7.
+pub fn exp2_32(a:f32) -> f32 { exp2_with(a, exp2_71s) }
+/// e^a, max relative error 8.6e-5.
+pub fn exp32_fast(a:f32) -> f32 { exp_with(a, exp2_41s) }
+/// e^a, max relative error 2.5e-7.
+pub fn exp32(a:f32) -> f32 { exp_with(a, exp2_71s) }
+/// log2(a), max absolute error 1.2e-5 within [0.5, 2], max relative error 7.5e-6 elsewhere.
+pub fn log2_32_fast(a:f32) -> f32 { log2_with(a, log2_46s) }
+/// log2(a), max absolute error 1.2e-7 within [0.5, 2], max relative error 1e-7 elsewhere.
+pub fn log2_32(a:f32) -> f32 { log2_with(a, log2_91s) }
+/// ln(a), max absolute error 8e-6 within [0.5, 2], max relative error 7.5e-6 elsewhere.
+pub fn ln32_fast(a:f32) -> f32 { log2_with(a, log2_46s) * LN_2 }
+/// ln(a), max absolute error 1e-7 within [0.5, 2], max relative error 1.6e-7 elsewhere.
+pub fn ln32(a:f32) -> f32 { log2_with(a, log2_91s) * LN_2 }
+/// a^b, max relative error 1.2e-4 while |b*log2(a)| <= 16.
+pub fn pow32_fast(a:f32, b:f32) -> f32 { pow_with(a, b, exp2_41s, log2_46s) }
+/// a^b, max relative error 1e-6 while |b*log2(a)| <= 16.
+pub fn pow32(a:f32, b:f32) -> f32 { pow_with(a, b, exp2_71s, log2_91s) }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::accuracy::ErrorStats;
+
+	/// Max relative error of `f` against the `f64` reference over 1000001 points of [from, to].
+	fn max_rel_error(f:impl FnMut(f32) -> f32, reference:impl FnMut(f64) -> f64, from:f32, to:f32) -> f64 {
+		return ErrorStats::sweep(f, reference, from, to, 1_000_000).max_rel;
+	}
+	/// Max absolute error of `f` against the `f64` reference over 1000001 points of [from, to].
+	fn max_abs_error(f:impl FnMut(f32) -> f32, reference:impl FnMut(f64) -> f64, from:f32, to:f32) -> f64 {
+		return ErrorStats::sweep(f, reference, from, to, 1_000_000).max_abs;
+	}
+
+	#[test]
+	fn exp_error_bounds() {
+		assert!(max_rel_error(exp2_32_fast, f64::exp2, -125.0, 127.0) <= 8.6e-5);
+		assert!(max_rel_error(exp2_32     , f64::exp2, -125.0, 127.0) <= 2e-7);
+		assert!(max_rel_error(exp32_fast  , f64::exp , -87.0 , 88.0 ) <= 8.6e-5);
+		assert!(max_rel_error(exp32       , f64::exp , -87.0 , 88.0 ) <= 2.5e-7);
+	}
+
+	#[test]
+	fn log_error_bounds() {
+		assert!(max_abs_error(log2_32_fast, f64::log2, 0.5  , 2.0 ) <= 1.2e-5);
+		assert!(max_abs_error(log2_32     , f64::log2, 0.5  , 2.0 ) <= 1.2e-7);
+		assert!(max_rel_error(log2_32_fast, f64::log2, 2.0  , 1e30) <= 7.5e-6);
+		assert!(max_rel_error(log2_32     , f64::log2, 2.0  , 1e30) <= 1e-7);
+		assert!(max_rel_error(log2_32_fast, f64::log2, 1e-38, 0.5 ) <= 7.5e-6);
+		assert!(max_rel_error(log2_32     , f64::log2, 1e-38, 0.5 ) <= 1e-7);
+		assert!(max_abs_error(ln32_fast   , f64::ln  , 0.5  , 2.0 ) <= 8e-6);
+		assert!(max_abs_error(ln32        , f64::ln  , 0.5  , 2.0 ) <= 1e-7);
+		assert!(max_rel_error(ln32_fast   , f64::ln  , 2.0  , 1e30) <= 7.5e-6);
+		assert!(max_rel_error(ln32        , f64::ln  , 2.0  , 1e30) <= 1.6e-7);
+	}
+
+	#[test]
+	fn pow_error_bounds() {
+		assert!(max_rel_error(|b| pow32_fast(1.7, b), |b| 1.7f64.powf(b), -20.0, 20.0) <= 1.2e-4);
+		assert!(max_rel_error(|b| pow32     (1.7, b), |b| 1.7f64.powf(b), -20.0, 20.0) <= 1e-6);
+		assert!(max_rel_error(|a| pow32_fast(a, 2.5), |a| a.powf(2.5)   , 0.02 , 80.0) <= 1.2e-4);
+		assert!(max_rel_error(|a| pow32     (a, 2.5), |a| a.powf(2.5)   , 0.02 , 80.0) <= 1e-6);
+	}
+
+	#[test]
+	fn special_values() {
+		assert_eq!(exp2_32(128.0), f32::INFINITY);
+		assert_eq!(exp2_32(-200.0), 0.0);
+		assert!(exp32(f32::NAN).is_nan());
+		assert_eq!(exp2_32(10.0), 1024.0);
+		assert_eq!(exp32(0.0), 1.0);
+		assert_eq!(log2_32(0.0), f32::NEG_INFINITY);
+		assert!(log2_32(-1.0).is_nan());
+		assert_eq!(log2_32(f32::INFINITY), f32::INFINITY);
+		assert_eq!(log2_32(8.0), 3.0);
+		assert!((pow32(-2.0, 3.0) + 8.0).abs() < 1e-5);
+		assert!((pow32(-2.0, 2.0) - 4.0).abs() < 1e-5);
+		assert!(pow32(-2.0, 0.5).is_nan());
+		assert_eq!(pow32(0.0, 2.0), 0.0);
+		assert_eq!(pow32(0.0, -2.0), f32::INFINITY);
+		assert_eq!(pow32(-0.0, 3.0).to_bits(), (-0.0f32).to_bits());
+		assert_eq!(pow32(-0.0, -3.0), f32::NEG_INFINITY);
+		assert_eq!(pow32(-0.0, 2.0).to_bits(), 0f32.to_bits());
+		assert_eq!(pow32(-0.0, 0.5).to_bits(), 0f32.to_bits());
+	}
+
+	#[test]
+	fn subnormal_results() {
+		assert_eq!(exp2_32(-140.0), f32::from_bits(1 << 9)); // 2^-140
+		assert_eq!(exp2_32(-149.0), f32::from_bits(1));
+		for a in [-87.36f32, -87.4, -90.0, -100.0, -103.0] {
+			let r = (a as f64).exp();
+			let ulp = f32::from_bits(1) as f64;
+			assert!((exp32(a) as f64 - r).abs() <= r * 2.5e-7 + ulp, "{}", a);
+			assert!((exp32_fast(a) as f64 - r).abs() <= r * 8.6e-5 + ulp, "{}", a);
+		}
+		assert_eq!(exp32(-104.5), 0.0);
+	}
+}
@@ -115,85 +115,91 @@ mod xx_hasher_64 {
 	const P4:u64 = 0x85EBCA77C2B2AE63;
 	const P5:u64 = 0x27D4EB2F165667C5;
 
-	// fn init(seed:u64) -> [u64;4] {
-	// 	[
-	// 		seed + P1 + P2,
-	// 		seed + P2,
-	// 		seed,
-	// 		seed - P1,
-	// 	]
-	// }
-
-	// fn stripe(v:&mut [u64;4]) {
-	// 	for s:[u64;4] in data {
-	// 		v[0] = (v[0] + (s[0] * P2)).rotate_left(31) * P1;
-	// 		v[1] = (v[1] + (s[1] * P2)).rotate_left(31) * P1;
-	// 		v[2] = (v[2] + (s[2] * P2)).rotate_left(31) * P1;
-	// 		v[3] = (v[3] + (s[3] * P2)).rotate_left(31) * P1;
-	// 	}
-	// }
-
-	// fn merge(v:&mut [u64;4]) {
-	// 	hash = v1.rotate_left( 1) +
-	// 				v2.rotate_left( 7) +
-	// 				v3.rotate_left(12) +
-	// 				v4.rotate_left(18);
-
-	// 	hash = (hash ^ (v1 * P2).rotate_left(31) * P1) * P1 + P4;
-	// 	hash = (hash ^ (v2 * P2).rotate_left(31) * P1) * P1 + P4;
-	// 	hash = (hash ^ (v3 * P2).rotate_left(31) * P1) * P1 + P4;
-	// 	hash = (hash ^ (v4 * P2).rotate_left(31) * P1) * P1 + P4;
-	// }
+	#[inline(always)]
+	fn round(acc:u64, v:u64) -> u64 {
+		acc.wrapping_add(v.wrapping_mul(P2)).rotate_left(31).wrapping_mul(P1)
+	}
 
-	// fn last_8_15() {
-	// 	for v:u64 in data {
-	// 		hash = hash ^ ((v * P2).rotate_left(31) * P1);
-	// 		hash = hash.rotate_left(27) * P1 + P4;
-	// 	}
-	// }
+	#[inline(always)]
+	fn merge_round(hash:u64, v:u64) -> u64 {
+		(hash ^ round(0, v)).wrapping_mul(P1).wrapping_add(P4)
+	}
 
-	// fn last_4_7() {
-	// 	for v:u32 in data {
-	// 		hash = (hash ^ (v as u64 * P1)).rotate_left(23) * P2 + P3;
-	// 	}
-	// }
+	#[inline(always)]
+	fn fetch64(input:&[u8], at:usize) -> u64 {
+		let mut ret = [0u8; 8];
+		ret.copy_from_slice(&input[at..at+8]);
+		return u64::from_le_bytes(ret);
+	}
 
-	// fn last_0_3() {
-	// 	for v:u8 in data {
-	// 		hash = (hash ^ (v as u64 * P5)).rotate_left(11) * P1;
-	// 	}
-	// }
+	#[inline(always)]
+	fn fetch32(input:&[u8], at:usize) -> u32 {
+		let mut ret = [0u8; 4];
+		ret.copy_from_slice(&input[at..at+4]);
+		return u32::from_le_bytes(ret);
+	}
 
 	#[inline(always)]
 	fn finalize(mut hash:u64) -> u64 {
 		hash ^= hash >> 33;
-		hash *= P2;
+		hash = hash.wrapping_mul(P2);
 		hash ^= hash >> 29;
-		hash *= P3;
+		hash = hash.wrapping_mul(P3);
 		hash ^= hash >> 32;
 		return hash;
 	}
 
-	// pub fn hash(seed:u64, input:&[u8]) -> u64 {
-	// 	let mut hash:u64 = 0;
-	
-	// 	if remaining >= 32 {
-	// 		let v = init(seed);
-	
-	// 		stripe(v);
-	// 		merge(v);
-	// 	} else {
-	// 		hash = seed + P5;
-	// 	}
-	
-	// 	hash += inputLength as u32;
-	
-	// 	last_8_15(&mut hash);
-	// 	last_4_7(&mut hash);
-	// 	last_0_3(&mut hash);
-	
-	// 	return finalize(hash);
-	// }
+	pub fn hash(seed:u64, input:&[u8]) -> u64 {
+		let len = input.len();
+		let mut p = 0;
+		let mut hash;
+
+		if len >= 32 {
+			let mut v = [
+				seed.wrapping_add(P1).wrapping_add(P2),
+				seed.wrapping_add(P2),
+				seed,
+				seed.wrapping_sub(P1),
+			];
+			while p + 32 <= len {
+				v[0] = round(v[0], fetch64(input, p     ));
+				v[1] = round(v[1], fetch64(input, p +  8));
+				v[2] = round(v[2], fetch64(input, p + 16));
+				v[3] = round(v[3], fetch64(input, p + 24));
+				p += 32;
+			}
+			hash = v[0].rotate_left( 1)
+				.wrapping_add(v[1].rotate_left( 7))
+				.wrapping_add(v[2].rotate_left(12))
+				.wrapping_add(v[3].rotate_left(18));
+			hash = merge_round(hash, v[0]);
+			hash = merge_round(hash, v[1]);
+			hash = merge_round(hash, v[2]);
+			hash = merge_round(hash, v[3]);
+		} else {
+			hash = seed.wrapping_add(P5);
+		}
+
+		hash = hash.wrapping_add(len as u64);
+
+		while p + 8 <= len {
+			hash ^= round(0, fetch64(input, p));
+			hash = hash.rotate_left(27).wrapping_mul(P1).wrapping_add(P4);
+			p += 8;
+		}
+		if p + 4 <= len {
+			hash ^= (fetch32(input, p) as u64).wrapping_mul(P1);
+			hash = hash.rotate_left(23).wrapping_mul(P2).wrapping_add(P3);
+			p += 4;
+		}
+		while p < len {
+			hash ^= (input[p] as u64).wrapping_mul(P5);
+			hash = hash.rotate_left(11).wrapping_mul(P1);
+			p += 1;
+		}
+
+		return finalize(hash);
+	}
 
 	pub fn hash8(seed:u64, v:u8) -> u64 {
 		let mut hash = seed + P5 + 1;
@@ -458,9 +464,27 @@ impl HasherTrait<u64, u64> for HasherXX64 {
 	#[inline(always)] fn hash_u64(seed:u64, v:u64) -> u64 { xx_hasher_64::hash64(seed, v) }
 }
 
+impl HasherXX64 {
+	/// XXH64 of an arbitrary byte string.
+	#[inline(always)]
+	pub fn hash_bytes(seed:u64, v:&[u8]) -> u64 { xx_hasher_64::hash(seed, v) }
+}
+
 impl HasherTrait<u32, u32> for HasherMurMur2A {
 	#[inline(always)] fn hash_u8( seed:u32, v:u8 ) -> u32 { murmur2a::hash8( seed, v) }
 	#[inline(always)] fn hash_u16(seed:u32, v:u16) -> u32 { murmur2a::hash16(seed, v) }
 	#[inline(always)] fn hash_u32(seed:u32, v:u32) -> u32 { murmur2a::hash32(seed, v) }
 	#[inline(always)] fn hash_u64(seed:u32, v:u64) -> u32 { murmur2a::hash64(seed, v) }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Reference values from the xxHash specification.
+	#[test]
+	fn xx64_matches_reference() {
+		assert_eq!(HasherXX64::hash_bytes(0, b""), 0xEF46DB3751D8E999);
+		assert_eq!(HasherXX64::hash_bytes(0, b"abc"), 0x44BC2CF5AD770999);
+	}
+}
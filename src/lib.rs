@@ -1,14 +1,51 @@
+//! Math functions for `no_std` projects.
+//!
+//! # Target support
+//!
+//! `prng::AtomicRNG` and `prng::GLOBAL_PRNG` only exist with `cfg(target_has_atomic = "32")`, so not on targets
+//! without atomics like thumbv6m. `prng::MSWS` and `prng::CounterRNG` work everywhere.
+//!
+//! # Migrating from 0.0.2
+//!
+//! `vector::Vec` has an associated `type Scalar`, `f32` for `Vec1` to `Vec4` and `f64` for `DVec1` to `DVec4`.
+//! Scalars in generic code are `V::Scalar` now, and code relying on `f32` has to ask for it:
+//! ```
+//! use tiny_lib::vector::*;
+//! fn scaled_len<V:Vec<Scalar = f32>>(mut v:V, s:f32) -> f32 {
+//!     v *= s;
+//!     return v.len();
+//! }
+//! assert_eq!(scaled_len(Vec2::new(3.0, 4.0), 2.0), 10.0);
+//! ```
+//! Implementations of `Vec` outside of this crate have to add `type Scalar = f32;`.
+//!
+//! `util::projection_matrix` takes its field of view as an angle, so `90.0` becomes `Deg(90.0)`.
+//! A plain `f32` only converts into `Rad`, which `projection_matrix` doesn't accept, so old call sites fail to
+//! compile instead of being read in radians.
+
 #![no_std]
 #![crate_type="lib"]
-#![feature(const_fn)]
-#![feature(const_mut_refs)]
-#![feature(clamp)]
-#![feature(test)]
-#![feature(const_fn_floating_point_arithmetic)]
+#![cfg_attr(all(test, feature = "bench"), feature(test))]
+
+#[cfg(test)]
+extern crate std;
 
+mod math;
 pub mod util;
+pub mod roots;
 pub mod trig;
+pub mod angle;
+pub mod batch;
+pub mod lookup;
+pub mod cordic;
+pub mod accuracy;
+pub mod exp;
+pub mod hyperbolic;
 pub mod vector;
 pub mod matrices;
 pub mod prng;
-pub mod hasher;
\ No newline at end of file
+pub mod hasher;
+pub mod sequences;
+pub mod sampling;
+#[cfg(test)]
+mod prng_statistics;
\ No newline at end of file
@@ -0,0 +1,172 @@
+use crate::trig::TAU;
+
+/// A quarter of a turn in fixed-point turns, where `u32` wraps around once per full turn.
+pub const TURN_QUARTER:u32 = 1 << 30;
+
+/// Sine of `i/n` of a turn, precise to f64 for any `i`.
+const fn sin_turn_f64(i:usize, n:usize) -> f64 {
+	// Map to [-PI, PI], where a Taylor series up to x^35 is exact to f64.
+	let i = if i > n / 2 { i as f64 - n as f64 } else { i as f64 };
+	let x = i * (core::f64::consts::TAU / n as f64);
+	let x2 = x * x;
+	let mut term = x;
+	let mut ret = x;
+	let mut k = 2;
+	while k < 36 {
+		term = -term * x2 / ((k * (k + 1)) as f64);
+		ret += term;
+		k += 2;
+	}
+	return ret;
+}
+
+/// Fixed-point turns of an angle in radians. Accurate for `|a|` well below 2^24.
+#[inline(always)]
+pub fn rad_to_turns(a:f32) -> u32 {
+	const SCALE:f32 = 4294967296.0 / TAU; // 2^32 / TAU
+	return (a * SCALE) as i64 as u32;
+}
+
+/// Sine table with `N` entries over a full turn and `f32` output, interpolated linearly.
+/// `N` must be a power of two, at least 4. Max error is about `(2*PI/N)^2 / 8`, which is 7.6e-5 for 256 and
+/// 4.8e-6 for 1024 entries.
+///
+/// The table is built at compile time and is meant to live in a `static`:
+/// ```
+/// use tiny_lib::lookup::*;
+/// static SIN:SinTable<256> = SinTable::new();
+/// assert!((SIN.cos(1.0) - 0.5403023).abs() < 1e-4);
+/// ```
+pub struct SinTable<const N:usize> {
+	table:[f32; N],
+}
+
+impl<const N:usize> SinTable<N> {
+	const BITS:u32 = N.trailing_zeros();
+
+	pub const fn new() -> Self {
+		assert!(N.is_power_of_two() && N >= 4, "table size must be a power of two, at least 4");
+		let mut table = [0f32; N];
+		let mut i = 0;
+		while i < N {
+			table[i] = sin_turn_f64(i, N) as f32;
+			i += 1;
+		}
+		return Self { table };
+	}
+
+	/// Sine of an angle in fixed-point turns.
+	#[inline(always)]
+	pub fn sin_turns(&self, a:u32) -> f32 {
+		const FRACTION:f32 = 1.0 / 4294967296.0; // 2^-32
+		let i = (a >> (32 - Self::BITS)) as usize;
+		let t = (a << Self::BITS) as f32 * FRACTION;
+		let s0 = self.table[i];
+		let s1 = self.table[(i + 1) & (N - 1)];
+		return s0 + (s1 - s0) * t;
+	}
+	/// Cosine of an angle in fixed-point turns.
+	#[inline(always)]
+	pub fn cos_turns(&self, a:u32) -> f32 {
+		return self.sin_turns(a.wrapping_add(TURN_QUARTER));
+	}
+	/// Sine of an angle in radians.
+	#[inline(always)]
+	pub fn sin(&self, a:f32) -> f32 {
+		return self.sin_turns(rad_to_turns(a));
+	}
+	/// Cosine of an angle in radians.
+	#[inline(always)]
+	pub fn cos(&self, a:f32) -> f32 {
+		return self.cos_turns(rad_to_turns(a));
+	}
+}
+
+impl<const N:usize> Default for SinTable<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// Sine table with `N` entries over a full turn and Q1.15 output, using only integer arithmetic.
+/// `N` must be a power of two within [4, 65536]. Max error is that of `SinTable` plus 2 in the last place.
+pub struct SinTableQ15<const N:usize> {
+	table:[i16; N],
+}
+
+impl<const N:usize> SinTableQ15<N> {
+	const BITS:u32 = N.trailing_zeros();
+
+	pub const fn new() -> Self {
+		assert!(N.is_power_of_two() && N >= 4 && N <= 65536, "table size must be a power of two within [4, 65536]");
+		let mut table = [0i16; N];
+		let mut i = 0;
+		while i < N {
+			let s = sin_turn_f64(i, N) * 32768.0;
+			table[i] = (if s < 0.0 { s - 0.5 } else { s + 0.5 }).min(i16::MAX as f64) as i16; // 1.0 saturates
+			i += 1;
+		}
+		return Self { table };
+	}
+
+	/// Sine of an angle in fixed-point turns, in Q1.15.
+	#[inline(always)]
+	pub fn sin(&self, a:u32) -> i16 {
+		let i = (a >> (32 - Self::BITS)) as usize;
+		let t = ((a << Self::BITS) >> 16) as i32;
+		let s0 = self.table[i] as i32;
+		let s1 = self.table[(i + 1) & (N - 1)] as i32;
+		// |s1 - s0| is at most 32768 for N >= 4, so the product fits i32.
+		return (s0 + (((s1 - s0) * t) >> 16)) as i16;
+	}
+	/// Cosine of an angle in fixed-point turns, in Q1.15.
+	#[inline(always)]
+	pub fn cos(&self, a:u32) -> i16 {
+		return self.sin(a.wrapping_add(TURN_QUARTER));
+	}
+}
+
+impl<const N:usize> Default for SinTableQ15<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	static SIN_256:SinTable<256> = SinTable::new();
+	static SIN_1024:SinTable<1024> = SinTable::new();
+	static SIN_Q15:SinTableQ15<256> = SinTableQ15::new();
+
+	#[test]
+	fn interpolation_error_bounds() {
+		let mut err_256 = 0f64;
+		let mut err_1024 = 0f64;
+		let mut err_q15 = 0f64;
+		for i in -100_000..=100_000 {
+			let a = i as f32 * 0.0001;
+			let (sin, cos) = ((a as f64).sin(), (a as f64).cos());
+			err_256 = err_256.max((SIN_256.sin(a) as f64 - sin).abs()).max((SIN_256.cos(a) as f64 - cos).abs());
+			err_1024 = err_1024.max((SIN_1024.sin(a) as f64 - sin).abs()).max((SIN_1024.cos(a) as f64 - cos).abs());
+			let turns = rad_to_turns(a);
+			err_q15 = err_q15.max((SIN_Q15.sin(turns) as f64 / 32768.0 - sin).abs()).max((SIN_Q15.cos(turns) as f64 / 32768.0 - cos).abs());
+		}
+		assert!(err_256 <= 7.6e-5, "{}", err_256);
+		assert!(err_1024 <= 4.8e-6, "{}", err_1024);
+		assert!(err_q15 <= 7.6e-5 + 2.0 / 32768.0, "{}", err_q15);
+	}
+
+	#[test]
+	fn exact_at_table_entries() {
+		assert_eq!(SIN_256.sin_turns(0), 0.0);
+		assert_eq!(SIN_256.sin_turns(TURN_QUARTER), 1.0);
+		assert_eq!(SIN_256.cos_turns(TURN_QUARTER * 2), -1.0);
+		assert_eq!(SIN_Q15.sin(TURN_QUARTER * 3), -32768);
+		assert_eq!(SIN_Q15.cos(0), 32767);
+		assert!(SIN_Q15.sin(u32::MAX).abs() <= 1); // interpolated between the last entry and the first
+		assert_eq!(SinTable::<256>::default().table, SIN_256.table);
+		assert_eq!(SinTableQ15::<256>::default().table, SIN_Q15.table);
+	}
+}
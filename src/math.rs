@@ -0,0 +1,192 @@
+// Backend for the few float functions, which `core` doesn't provide.
+//
+// By default everything is implemented by this crate, so it builds for bare-metal targets without libm.
+// With the `libm` feature, calls are routed to the `libm` crate instead, trading speed for correct rounding.
+
+#[cfg(not(feature = "libm"))]
+use crate::trig::*;
+
+/// Absolute value, by clearing the sign bit.
+#[inline(always)]
+pub fn abs(a:f32) -> f32 {
+	f32::from_bits(a.to_bits() & 0x7FFFFFFF)
+}
+
+/// Rounding and sign functions of `f32` and `f64`, which `core` doesn't provide.
+pub(crate) trait Float : Copy {
+	/// Absolute value, by clearing the sign bit.
+	fn abs(self) -> Self;
+	/// Rounds towards zero, keeping the sign of zero.
+	fn trunc(self) -> Self;
+	/// Rounds down.
+	fn floor(self) -> Self;
+	/// Rounds up.
+	fn ceil(self) -> Self;
+	/// Rounds to the nearest integer, halves away from zero.
+	fn round(self) -> Self;
+	/// 1 with the sign of `self`, so -1 for -0.0, or NaN.
+	fn signum(self) -> Self;
+}
+
+macro_rules! impl_float {
+	(
+		$($t:ty, $i:ty; $whole:expr; $sign:expr);+
+	) => {
+		$(
+			impl Float for $t {
+				#[inline(always)]
+				fn abs(self) -> $t {
+					<$t>::from_bits(self.to_bits() & !$sign)
+				}
+				#[inline(always)]
+				#[allow(clippy::neg_cmp_op_on_partial_ord)] // NaN has to be returned as is
+				fn trunc(self) -> $t {
+					if !(Float::abs(self) < $whole) { // from which on every float is whole, and NaN
+						return self;
+					}
+					return <$t>::from_bits(((self as $i) as $t).to_bits() | (self.to_bits() & $sign));
+				}
+				#[inline(always)]
+				fn floor(self) -> $t {
+					let t = Float::trunc(self);
+					return if t > self { t - 1.0 } else { t };
+				}
+				#[inline(always)]
+				fn ceil(self) -> $t {
+					let t = Float::trunc(self);
+					return if t < self { t + 1.0 } else { t };
+				}
+				#[inline(always)]
+				fn round(self) -> $t {
+					let t = Float::trunc(self);
+					if Float::abs(self - t) >= 0.5 { // exact, as both are within one of each other
+						return if self < 0.0 { t - 1.0 } else { t + 1.0 };
+					}
+					return t;
+				}
+				#[inline(always)]
+				fn signum(self) -> $t {
+					if self.is_nan() {
+						return self;
+					}
+					return <$t>::from_bits((1.0 as $t).to_bits() | (self.to_bits() & $sign));
+				}
+			}
+		)+
+	};
+}
+impl_float!(
+	f32, i32; 8388608.0; 0x80000000; // 2^23
+	f64, i64; 4503599627370496.0; 0x8000000000000000 // 2^52
+);
+
+/// Square root, correctly rounded.
+#[cfg(not(feature = "libm"))]
+#[allow(clippy::neg_cmp_op_on_partial_ord)] // NaN has to take the first branch
+pub fn sqrt(a:f32) -> f32 {
+	if !(a > 0.0) {
+		return if a == 0.0 { a } else { f32::NAN }; // keeps -0.0
+	}
+	if a == f32::INFINITY {
+		return a;
+	}
+	// Halving the exponent through the bits is within 6%, so 4 Newton steps reach f64 precision,
+	// which makes the final rounding to f32 correct. Subnormal f32 are normal in f64.
+	let a = a as f64;
+	let mut y = f64::from_bits((a.to_bits() >> 1) + 0x1FF8000000000000);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	return y as f32;
+}
+#[cfg(feature = "libm")]
+#[inline(always)]
+pub fn sqrt(a:f32) -> f32 { libm::sqrtf(a) }
+
+/// Square root, within 1 in the last place.
+#[cfg(not(feature = "libm"))]
+#[allow(clippy::neg_cmp_op_on_partial_ord)] // NaN has to take the first branch
+pub fn sqrt64(a:f64) -> f64 {
+	if !(a > 0.0) {
+		return if a == 0.0 { a } else { f64::NAN }; // keeps -0.0
+	}
+	if a == f64::INFINITY {
+		return a;
+	}
+	// Subnormals are scaled by 2^54 first, so halving the exponent through the bits is within 6% again,
+	// and 5 Newton steps reach the last place.
+	let (a, scale) = if a < f64::MIN_POSITIVE { (a * 18014398509481984.0, 1.0 / 134217728.0) } else { (a, 1.0) };
+	let mut y = f64::from_bits((a.to_bits() >> 1) + 0x1FF8000000000000);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	y = 0.5 * (y + a / y);
+	return y * scale;
+}
+#[cfg(feature = "libm")]
+#[inline(always)]
+pub fn sqrt64(a:f64) -> f64 { libm::sqrt(a) }
+
+#[cfg(not(feature = "libm"))]
+#[inline(always)]
+pub fn sincos(a:f32) -> (f32, f32) { sincos32(a) }
+#[cfg(feature = "libm")]
+#[inline(always)]
+pub fn sincos(a:f32) -> (f32, f32) { libm::sincosf(a) }
+
+#[cfg(not(feature = "libm"))]
+#[inline(always)]
+pub fn tan(a:f32) -> f32 { tan32(a) }
+#[cfg(feature = "libm")]
+#[inline(always)]
+pub fn tan(a:f32) -> f32 { libm::tanf(a) }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn sqrt_is_correctly_rounded() {
+		for bits in (1..0x7F800000u32).step_by(997) {
+			let a = f32::from_bits(bits);
+			assert_eq!(sqrt(a), (a as f64).sqrt() as f32);
+		}
+		assert_eq!(sqrt(0.0), 0.0);
+		assert!(sqrt(-1.0).is_nan());
+		assert_eq!(sqrt(f32::INFINITY), f32::INFINITY);
+	}
+
+	#[test]
+	fn sqrt64_error_bound() {
+		let mut bits = 1u64;
+		while bits < 0x7FF0000000000000 {
+			let a = f64::from_bits(bits);
+			let (y, expected) = (sqrt64(a), a.sqrt());
+			assert!((y.to_bits() as i64 - expected.to_bits() as i64).abs() <= 1, "{} {} {}", a, y, expected);
+			bits += 0x0000_0123_4567_89AB;
+		}
+		assert_eq!((sqrt64(0.0), sqrt64(4.0), sqrt64(f64::INFINITY)), (0.0, 2.0, f64::INFINITY));
+		assert!(sqrt64(-1.0).is_nan());
+	}
+
+	#[test]
+	fn rounding_matches_std() {
+		let special = [0.0, -0.0, 0.5, -0.5, 1.5, -2.5, 0.49999997, -0.49999997, 8388607.5, -8388609.0, 1e30, f32::INFINITY];
+		let stepped = (0..0xFF800000u32).step_by(9973).map(f32::from_bits).filter(|a| !a.is_nan());
+		for a in special.iter().copied().chain(stepped) {
+			let b = a as f64 + if a.abs() < 1e15 { a as f64 * 1e-9 } else { 0.0 }; // f64 values between the f32 ones
+			assert_eq!(Float::trunc(a).to_bits(), a.trunc().to_bits(), "{}", a);
+			assert_eq!(Float::floor(a).to_bits(), a.floor().to_bits(), "{}", a);
+			assert_eq!(Float::ceil(a).to_bits(), a.ceil().to_bits(), "{}", a);
+			assert_eq!(Float::round(a).to_bits(), a.round().to_bits(), "{}", a);
+			assert_eq!(Float::signum(a).to_bits(), a.signum().to_bits(), "{}", a);
+			assert_eq!(Float::floor(b).to_bits(), b.floor().to_bits(), "{}", b);
+			assert_eq!(Float::ceil(b).to_bits(), b.ceil().to_bits(), "{}", b);
+			assert_eq!(Float::round(b).to_bits(), b.round().to_bits(), "{}", b);
+		}
+		assert_eq!(Float::round(0.49999999999999994f64), 0.0);
+		assert!(Float::floor(f32::NAN).is_nan() && Float::round(f64::NAN).is_nan() && Float::signum(f32::NAN).is_nan());
+	}
+}
@@ -1,7 +1,31 @@
 // Middle Square Weyl Sequence PRNG
 
+use crate::vector::*;
+use crate::hasher::*;
+#[cfg(target_has_atomic = "32")]
+use core::sync::atomic::Ordering;
+
+// Counter of `AtomicRNG`. Targets like thumbv7m only have 32-bit atomics, where it wraps after 2^32 draws.
+#[cfg(target_has_atomic = "64")]
+type AtomicCtr = core::sync::atomic::AtomicU64;
+#[cfg(target_has_atomic = "64")]
+type Ctr = u64;
+#[cfg(target_has_atomic = "64")]
+#[inline(always)]
+fn widen_ctr(ctr:Ctr) -> u64 { ctr }
+#[cfg(all(target_has_atomic = "32", not(target_has_atomic = "64")))]
+type AtomicCtr = core::sync::atomic::AtomicU32;
+#[cfg(all(target_has_atomic = "32", not(target_has_atomic = "64")))]
+type Ctr = u32;
+#[cfg(all(target_has_atomic = "32", not(target_has_atomic = "64")))]
+#[inline(always)]
+fn widen_ctr(ctr:Ctr) -> u64 { ctr as u64 }
+
 const WEYL_CONST:u64 = 0xb5ad4eceda1ce2a9;
 
+/// Version of the serialised generator state layout. Bumped whenever the layout changes.
+pub(crate) const STATE_VERSION:u8 = 1;
+
 pub type RNG = MSWS;
 
 pub struct MSWS {
@@ -41,6 +65,45 @@ impl MSWS {
 	pub fn new_seeded(&mut self) -> MSWS {
 		MSWS::new(self.get_u64())
 	}
+	/// Seeds the generator with the XXH64 hash of arbitrary bytes.
+	pub fn from_seed_bytes(seed:&[u8]) -> MSWS {
+		MSWS::new(HasherXX64::hash_bytes(0, seed))
+	}
+	/// Seeds the generator with a human-readable string, such as a level name.
+	pub fn from_seed_str(seed:&str) -> MSWS {
+		MSWS::from_seed_bytes(seed.as_bytes())
+	}
+	/// Independent stream, identified by a label.
+	/// Unlike `new_seeded`, doesn't advance the generator,
+	/// so the result only depends on the seed of this generator and the label.
+	pub fn new_seeded_bytes(&self, label:&[u8]) -> MSWS {
+		MSWS::new(HasherXX64::hash_bytes(self.s, label))
+	}
+	pub fn new_seeded_str(&self, label:&str) -> MSWS {
+		self.new_seeded_bytes(label.as_bytes())
+	}
+
+	pub const STATE_SIZE:usize = 1 + 3 * 8;
+	/// Layout: version byte, followed by `x`, `w` and `s` as little-endian `u64`s.
+	pub fn to_bytes(&self) -> [u8; MSWS::STATE_SIZE] {
+		let mut ret = [0u8; MSWS::STATE_SIZE];
+		ret[0] = STATE_VERSION;
+		write_u64(&mut ret, 1     , self.x);
+		write_u64(&mut ret, 1 +  8, self.w);
+		write_u64(&mut ret, 1 + 16, self.s);
+		return ret;
+	}
+	/// Returns `None` if the state was written by an incompatible version.
+	pub fn from_bytes(bytes:&[u8; MSWS::STATE_SIZE]) -> Option<MSWS> {
+		if bytes[0] != STATE_VERSION {
+			return None;
+		}
+		return Some(MSWS {
+			x: read_u64(bytes, 1     ),
+			w: read_u64(bytes, 1 +  8),
+			s: read_u64(bytes, 1 + 16),
+		});
+	}
 }
 
 impl Default for MSWS {
@@ -49,4 +112,349 @@ impl Default for MSWS {
 	}
 }
 
-pub static mut GLOBAL_PRNG:RNG = RNG::new(0);
\ No newline at end of file
+#[inline(always)]
+pub(crate) fn write_u64(bytes:&mut [u8], at:usize, v:u64) {
+	bytes[at..at+8].copy_from_slice(&v.to_le_bytes());
+}
+#[inline(always)]
+pub(crate) fn read_u64(bytes:&[u8], at:usize) -> u64 {
+	let mut ret = [0u8; 8];
+	ret.copy_from_slice(&bytes[at..at+8]);
+	return u64::from_le_bytes(ret);
+}
+#[inline(always)]
+pub(crate) fn write_u32(bytes:&mut [u8], at:usize, v:u32) {
+	bytes[at..at+4].copy_from_slice(&v.to_le_bytes());
+}
+#[inline(always)]
+pub(crate) fn read_u32(bytes:&[u8], at:usize) -> u32 {
+	let mut ret = [0u8; 4];
+	ret.copy_from_slice(&bytes[at..at+4]);
+	return u32::from_le_bytes(ret);
+}
+
+/// Squares counter-based PRNG, by the author of MSWS.
+///
+/// Runs four middle square rounds over `ctr * key`, so every output depends only on the counter.
+///
+/// Source: https://arxiv.org/abs/2004.06278
+#[inline(always)]
+const fn squares32(ctr:u64, key:u64) -> u32 {
+	let y = ctr.wrapping_mul(key);
+	let z = y.wrapping_add(key);
+	let mut x = y;
+	x = x.wrapping_mul(x).wrapping_add(y); x = x.rotate_left(32); // round 1
+	x = x.wrapping_mul(x).wrapping_add(z); x = x.rotate_left(32); // round 2
+	x = x.wrapping_mul(x).wrapping_add(y); x = x.rotate_left(32); // round 3
+	return (x.wrapping_mul(x).wrapping_add(z) >> 32) as u32;      // round 4
+}
+
+/// 64-bit output variant of `squares32`, with a fifth round.
+///
+/// Source: https://arxiv.org/abs/2004.06278
+#[inline(always)]
+const fn squares64(ctr:u64, key:u64) -> u64 {
+	let y = ctr.wrapping_mul(key);
+	let z = y.wrapping_add(key);
+	let mut x = y;
+	x = x.wrapping_mul(x).wrapping_add(y); x = x.rotate_left(32);     // round 1
+	x = x.wrapping_mul(x).wrapping_add(z); x = x.rotate_left(32);     // round 2
+	x = x.wrapping_mul(x).wrapping_add(y); x = x.rotate_left(32);     // round 3
+	let t = x.wrapping_mul(x).wrapping_add(z); x = t.rotate_left(32); // round 4
+	return t ^ (x.wrapping_mul(x).wrapping_add(y) >> 32);             // round 5
+}
+
+/// Stateless generator: every value is a pure function of the seed and an index (or a cell),
+/// so it can be evaluated in any order, in parallel, and gives the same results on every platform.
+#[derive(Copy, Clone)]
+pub struct CounterRNG {
+	key: u64,
+	/// Separate key for `u64_at`, so its values are unrelated to the ones of `u32_at`.
+	key64: u64,
+}
+
+impl CounterRNG {
+	pub const fn new(seed:u64) -> CounterRNG {
+		// Squares needs a key with well mixed bits, which a raw seed like 0 or 1 is not.
+		let mut rng = MSWS::new(seed);
+		let lo = rng.get_u32() as u64;
+		let hi = rng.get_u32() as u64;
+		let lo64 = rng.get_u32() as u64;
+		let hi64 = rng.get_u32() as u64;
+		CounterRNG {
+			key: lo | (hi << 32) | 1,
+			key64: lo64 | (hi64 << 32) | 1,
+		}
+	}
+	#[inline(always)]
+	pub const fn u32_at(&self, index:u64) -> u32 {
+		squares32(index, self.key)
+	}
+	/// Has its own key, so `u64_at(i)` and `u32_at(j)` are unrelated for any `i` and `j`.
+	#[inline(always)]
+	pub const fn u64_at(&self, index:u64) -> u64 {
+		squares64(index, self.key64)
+	}
+	/// Range of [0.0, 1.0] inclusive.
+	#[inline(always)]
+	pub fn f32_at(&self, index:u64) -> f32 {
+		self.u32_at(index) as f32 / u32::MAX as f32
+	}
+	/// Seeds the generator with the XXH64 hash of arbitrary bytes.
+	pub fn from_seed_bytes(seed:&[u8]) -> CounterRNG {
+		CounterRNG::new(HasherXX64::hash_bytes(0, seed))
+	}
+	/// Seeds the generator with a human-readable string, such as a level name.
+	pub fn from_seed_str(seed:&str) -> CounterRNG {
+		CounterRNG::from_seed_bytes(seed.as_bytes())
+	}
+	/// Independent generator, identified by a label.
+	pub fn new_seeded_bytes(&self, label:&[u8]) -> CounterRNG {
+		CounterRNG::new(HasherXX64::hash_bytes(self.key, label))
+	}
+	pub fn new_seeded_str(&self, label:&str) -> CounterRNG {
+		self.new_seeded_bytes(label.as_bytes())
+	}
+	/// Value for an integer grid cell, such as a pixel or a tile.
+	#[inline(always)]
+	pub const fn u32_at_cell(&self, x:i32, y:i32) -> u32 {
+		self.u32_at(((x as u32 as u64) << 32) | (y as u32 as u64))
+	}
+	/// Range of [0.0, 1.0] inclusive.
+	#[inline(always)]
+	pub fn f32_at_cell(&self, x:i32, y:i32) -> f32 {
+		self.u32_at_cell(x, y) as f32 / u32::MAX as f32
+	}
+	/// Value for a point, keyed by the exact bits of its coordinates. `-0.0` is treated as `0.0`.
+	#[inline(always)]
+	pub fn u32_at_vec2(&self, cell:Vec2) -> u32 {
+		let x = (cell.x + 0f32).to_bits() as u64;
+		let y = (cell.y + 0f32).to_bits() as u64;
+		return self.u32_at((x << 32) | y);
+	}
+	/// Range of [0.0, 1.0] inclusive.
+	#[inline(always)]
+	pub fn f32_at_vec2(&self, cell:Vec2) -> f32 {
+		self.u32_at_vec2(cell) as f32 / u32::MAX as f32
+	}
+
+	pub const STATE_SIZE:usize = 1 + 2 * 8;
+	/// Layout: version byte, followed by `key` and `key64` as little-endian `u64`s.
+	pub fn to_bytes(&self) -> [u8; CounterRNG::STATE_SIZE] {
+		let mut ret = [0u8; CounterRNG::STATE_SIZE];
+		ret[0] = STATE_VERSION;
+		write_u64(&mut ret, 1    , self.key);
+		write_u64(&mut ret, 1 + 8, self.key64);
+		return ret;
+	}
+	/// Returns `None` if the state was written by an incompatible version.
+	pub fn from_bytes(bytes:&[u8; CounterRNG::STATE_SIZE]) -> Option<CounterRNG> {
+		if bytes[0] != STATE_VERSION {
+			return None;
+		}
+		return Some(CounterRNG {
+			key:   read_u64(bytes, 1    ),
+			key64: read_u64(bytes, 1 + 8),
+		});
+	}
+}
+
+/// Generator, that can be shared between threads and interrupt handlers without `unsafe`.
+///
+/// The whole state is a single atomic counter, which is advanced with one `fetch_add`,
+/// so concurrent callers never observe a torn state and every call consumes its own counter value.
+/// The outputs are still random 32-bit numbers, so equal values do occur.
+///
+/// The counter is 64-bit where the target has 64-bit atomics and 32-bit otherwise,
+/// in which case the sequence repeats after 2^32 draws.
+///
+/// Only available with `cfg(target_has_atomic = "32")`. Targets without atomics, like thumbv6m,
+/// can use `MSWS` behind their own critical section, or `CounterRNG` with an explicit index.
+#[cfg(target_has_atomic = "32")]
+pub struct AtomicRNG {
+	ctr: AtomicCtr,
+}
+
+#[cfg(target_has_atomic = "32")]
+impl AtomicRNG {
+	pub const fn new(seed:u64) -> AtomicRNG {
+		AtomicRNG {
+			ctr: AtomicCtr::new(seed.wrapping_mul(WEYL_CONST) as Ctr),
+		}
+	}
+	/// Restarts the sequence, as if the generator was created with `AtomicRNG::new(seed)`.
+	pub fn reseed(&self, seed:u64) {
+		self.ctr.store(seed.wrapping_mul(WEYL_CONST) as Ctr, Ordering::Relaxed);
+	}
+	pub fn get_u32(&self) -> u32 {
+		squares32(widen_ctr(self.ctr.fetch_add(1, Ordering::Relaxed)), WEYL_CONST)
+	}
+	pub fn get_u64(&self) -> u64 {
+		 (self.get_u32() as u64)        |
+		((self.get_u32() as u64) << 32)
+	}
+	/// Range of [0.0, 1.0] inclusive.
+	pub fn get_f32(&self) -> f32 {
+		self.get_u32() as f32 / u32::MAX as f32
+	}
+	pub fn new_seeded(&self) -> MSWS {
+		MSWS::new(self.get_u64())
+	}
+
+	pub const STATE_SIZE:usize = 1 + 8;
+	/// Layout: version byte, followed by the counter as a little-endian `u64`.
+	/// With a 32-bit counter only the low half is restored.
+	pub fn to_bytes(&self) -> [u8; AtomicRNG::STATE_SIZE] {
+		let mut ret = [0u8; AtomicRNG::STATE_SIZE];
+		ret[0] = STATE_VERSION;
+		write_u64(&mut ret, 1, widen_ctr(self.ctr.load(Ordering::Relaxed)));
+		return ret;
+	}
+	/// Returns `None` if the state was written by an incompatible version.
+	pub fn from_bytes(bytes:&[u8; AtomicRNG::STATE_SIZE]) -> Option<AtomicRNG> {
+		if bytes[0] != STATE_VERSION {
+			return None;
+		}
+		return Some(AtomicRNG {
+			ctr: AtomicCtr::new(read_u64(bytes, 1) as Ctr),
+		});
+	}
+	/// Same as `from_bytes`, but restores the state in place, so it works on `GLOBAL_PRNG`.
+	/// Returns `false` and leaves the generator untouched if the version is incompatible.
+	pub fn restore(&self, bytes:&[u8; AtomicRNG::STATE_SIZE]) -> bool {
+		if bytes[0] != STATE_VERSION {
+			return false;
+		}
+		self.ctr.store(read_u64(bytes, 1) as Ctr, Ordering::Relaxed);
+		return true;
+	}
+}
+
+#[cfg(target_has_atomic = "32")]
+impl Default for AtomicRNG {
+	fn default() -> AtomicRNG {
+		AtomicRNG::new(0)
+	}
+}
+
+/// Shared generator, seeded with 0 until `reseed` or `restore` is called.
+///
+/// Only available with `cfg(target_has_atomic = "32")`, like `AtomicRNG`.
+#[cfg(target_has_atomic = "32")]
+pub static GLOBAL_PRNG:AtomicRNG = AtomicRNG::new(0);
+
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn msws_restore_replays_sequence() {
+		let mut rng = MSWS::new(42);
+		for _ in 0..100 { rng.get_u32(); }
+		let state = rng.to_bytes();
+		let mut restored = MSWS::from_bytes(&state).unwrap();
+		for _ in 0..1000 {
+			assert_eq!(rng.get_u32(), restored.get_u32());
+		}
+	}
+
+	#[test]
+	fn atomic_restore_replays_sequence() {
+		let rng = AtomicRNG::new(42);
+		for _ in 0..100 { rng.get_u32(); }
+		let state = rng.to_bytes();
+		let restored = AtomicRNG::from_bytes(&state).unwrap();
+		let mut expected = [0u32; 1000];
+		for e in expected.iter_mut() {
+			*e = rng.get_u32();
+			assert_eq!(*e, restored.get_u32());
+		}
+		assert!(rng.restore(&state));
+		for e in expected.iter() {
+			assert_eq!(*e, rng.get_u32());
+		}
+	}
+
+	#[test]
+	fn state_format_is_stable() {
+		let state = MSWS::new(0).to_bytes();
+		assert_eq!(state[0], STATE_VERSION);
+		assert_eq!(&state[17..25], &WEYL_CONST.to_le_bytes());
+	}
+
+	#[test]
+	fn counter_rng_is_stateless() {
+		let rng = CounterRNG::new(5);
+		assert_eq!(rng.u32_at(17), CounterRNG::new(5).u32_at(17));
+		assert_ne!(rng.u32_at(17), CounterRNG::new(6).u32_at(17));
+		assert_ne!(rng.u32_at_cell(1, 2), rng.u32_at_cell(2, 1));
+		assert_eq!(rng.u32_at_vec2(Vec2::new(-0.0, 1.5)), rng.u32_at_vec2(Vec2::new(0.0, 1.5)));
+	}
+
+	/// Pinned outputs, so a change in the mapping is noticed, since saved worlds depend on it.
+	#[test]
+	fn counter_rng_is_reproducible() {
+		let rng = CounterRNG::new(0);
+		assert_eq!([rng.u32_at(0), rng.u32_at(1), rng.u32_at(u64::MAX)], [187440078, 2394010267, 163744865]);
+		assert_eq!([rng.u64_at(0), rng.u64_at(1)], [3883468803788002313, 8504115846819325024]);
+	}
+
+	#[test]
+	fn counter_rng_restore_replays_sequence() {
+		let rng = CounterRNG::from_seed_str("world");
+		let restored = CounterRNG::from_bytes(&rng.to_bytes()).unwrap();
+		for i in 0..1000 {
+			assert_eq!(rng.u32_at(i), restored.u32_at(i));
+			assert_eq!(rng.u64_at(i), restored.u64_at(i));
+		}
+	}
+
+	#[test]
+	fn counter_rng_u64_is_separate_stream() {
+		let rng = CounterRNG::new(3);
+		for i in 0..1000 {
+			let v = rng.u64_at(i);
+			assert_ne!(v as u32, rng.u32_at(2 * i));
+			assert_ne!((v >> 32) as u32, rng.u32_at(2 * i + 1));
+		}
+	}
+
+	#[test]
+	fn string_seeds_are_reproducible() {
+		let mut a = MSWS::from_seed_str("level 1");
+		let mut b = MSWS::from_seed_bytes(b"level 1");
+		assert_eq!(a.get_u64(), b.get_u64());
+		assert_ne!(MSWS::from_seed_str("level 1").get_u64(), MSWS::from_seed_str("level 2").get_u64());
+	}
+
+	#[test]
+	fn labeled_streams_do_not_depend_on_draws() {
+		let mut world = MSWS::from_seed_str("world");
+		let mut terrain = world.new_seeded_str("terrain");
+		let mut trees = world.new_seeded_str("trees");
+		assert_ne!(terrain.get_u64(), trees.get_u64());
+		let expected = terrain.get_u64();
+		for _ in 0..10 { world.get_u32(); }
+		let mut terrain = world.new_seeded_str("terrain");
+		terrain.get_u64();
+		assert_eq!(terrain.get_u64(), expected);
+
+		let counter = CounterRNG::from_seed_str("world");
+		assert_ne!(counter.new_seeded_str("terrain").u32_at(0), counter.new_seeded_str("trees").u32_at(0));
+	}
+
+	#[test]
+	fn rejects_unknown_version() {
+		let mut state = MSWS::new(1).to_bytes();
+		state[0] = STATE_VERSION.wrapping_add(1);
+		assert!(MSWS::from_bytes(&state).is_none());
+		let mut state = AtomicRNG::new(1).to_bytes();
+		state[0] = STATE_VERSION.wrapping_add(1);
+		assert!(AtomicRNG::from_bytes(&state).is_none());
+		assert!(!AtomicRNG::new(1).restore(&state));
+		let mut state = CounterRNG::new(1).to_bytes();
+		state[0] = STATE_VERSION.wrapping_add(1);
+		assert!(CounterRNG::from_bytes(&state).is_none());
+	}
+}
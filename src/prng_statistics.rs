@@ -0,0 +1,182 @@
+// Statistical quality tests for the PRNGs.
+//
+// Each test computes a statistic, which is (approximately) standard normal for an ideal generator,
+// and fails if it is more than `MAX_Z` standard deviations away from zero.
+// Seeds are fixed, so the suite is deterministic and a failure always means the generator changed.
+//
+// Source: D. Knuth, "The Art of Computer Programming", Vol. 2, 3.3.2
+// Source: G. Marsaglia, "DIEHARD" (birthday spacings)
+
+use crate::prng::*;
+
+/// Probability of a false positive is ~6e-7 per check.
+const MAX_Z:f64 = 5.0;
+
+trait Source {
+	fn next_u32(&mut self) -> u32;
+	fn next_f64(&mut self) -> f64 {
+		self.next_u32() as f64 / (1u64 << 32) as f64
+	}
+}
+impl Source for MSWS {
+	fn next_u32(&mut self) -> u32 { self.get_u32() }
+}
+impl Source for &AtomicRNG {
+	fn next_u32(&mut self) -> u32 { self.get_u32() }
+}
+/// Walks the indices in order, which is the worst case for a counter-based generator.
+impl Source for (CounterRNG, u64) {
+	fn next_u32(&mut self) -> u32 {
+		self.1 += 1;
+		return self.0.u32_at(self.1);
+	}
+}
+
+/// Normalises a chi-square statistic with `dof` degrees of freedom.
+fn chi_square_z(chi:f64, dof:usize) -> f64 {
+	(chi - dof as f64) / (2.0 * dof as f64).sqrt()
+}
+
+fn chi_square(observed:&[u64], expected:&[f64]) -> f64 {
+	let mut chi = 0.0;
+	for (o, e) in observed.iter().zip(expected.iter()) {
+		let d = *o as f64 - e;
+		chi += d * d / e;
+	}
+	return chi;
+}
+
+fn check(name:&str, generator:&str, z:f64) {
+	assert!(z.abs() < MAX_Z, "{} test failed for {}: z = {}", name, generator, z);
+}
+
+/// Frequencies of the top and the bottom byte.
+fn uniformity<S:Source>(generator:&str, mut s:S) {
+	const N:usize = 1 << 20;
+	let mut hi = [0u64; 256];
+	let mut lo = [0u64; 256];
+	for _ in 0..N {
+		let v = s.next_u32();
+		hi[(v >> 24) as usize] += 1;
+		lo[(v & 0xFF) as usize] += 1;
+	}
+	let expected = [N as f64 / 256.0; 256];
+	check("Chi-square (high byte)", generator, chi_square_z(chi_square(&hi, &expected), 255));
+	check("Chi-square (low byte)" , generator, chi_square_z(chi_square(&lo, &expected), 255));
+}
+
+/// Correlation coefficient between consecutive outputs, which is ~N(0, 1/N).
+fn serial_correlation<S:Source>(generator:&str, mut s:S) {
+	const N:usize = 1 << 20;
+	let first = s.next_f64();
+	let mut prev = first;
+	let (mut sum, mut sum_sq, mut sum_prod) = (0.0, 0.0, 0.0);
+	for _ in 0..N {
+		let v = s.next_f64();
+		sum += v;
+		sum_sq += v * v;
+		sum_prod += prev * v;
+		prev = v;
+	}
+	let n = N as f64;
+	let mean = sum / n;
+	let r = (sum_prod / n - mean * mean) / (sum_sq / n - mean * mean);
+	check("Serial correlation", generator, r * n.sqrt());
+}
+
+/// Wald-Wolfowitz runs above and below 0.5.
+fn runs<S:Source>(generator:&str, mut s:S) {
+	const N:usize = 1 << 20;
+	let mut above = 0u64;
+	let mut runs = 0u64;
+	let mut prev = None;
+	for _ in 0..N {
+		let bit = s.next_u32() >> 31 != 0;
+		above += bit as u64;
+		if prev != Some(bit) {
+			runs += 1;
+		}
+		prev = Some(bit);
+	}
+	let n1 = above as f64;
+	let n2 = (N as u64 - above) as f64;
+	let n = N as f64;
+	let mean = 2.0 * n1 * n2 / n + 1.0;
+	let var = (mean - 1.0) * (mean - 2.0) / (n - 1.0);
+	check("Runs", generator, (runs as f64 - mean) / var.sqrt());
+}
+
+/// Number of repeated spacings between sorted birthdays is ~Poisson(m^3 / 4n).
+fn birthday_spacings<S:Source>(generator:&str, mut s:S) {
+	const DAYS_BITS:u32 = 24;
+	const M:usize = 1 << 10;
+	const ROUNDS:usize = 200;
+	let lambda = (M * M * M) as f64 / (4u64 << DAYS_BITS) as f64;
+	let mut birthdays = [0u32; M];
+	let mut spacings = [0u32; M];
+	let mut repeats = 0u64;
+	for _ in 0..ROUNDS {
+		for b in birthdays.iter_mut() {
+			*b = s.next_u32() >> (32 - DAYS_BITS);
+		}
+		birthdays.sort_unstable();
+		spacings[0] = birthdays[0];
+		for i in 1..M {
+			spacings[i] = birthdays[i] - birthdays[i - 1];
+		}
+		spacings.sort_unstable();
+		for i in 1..M {
+			repeats += (spacings[i] == spacings[i - 1]) as u64;
+		}
+	}
+	let mean = lambda * ROUNDS as f64;
+	check("Birthday spacings", generator, (repeats as f64 - mean) / mean.sqrt());
+}
+
+/// Lengths of gaps between values in [0.25, 0.5) are geometrically distributed.
+fn gap<S:Source>(generator:&str, mut s:S) {
+	const GAPS:usize = 1 << 17;
+	const T:usize = 16;
+	const P:f64 = 0.25;
+	let mut observed = [0u64; T + 1];
+	let mut len = 0;
+	let mut gaps = 0;
+	while gaps < GAPS {
+		let v = s.next_f64();
+		if v >= 0.25 && v < 0.5 {
+			observed[if len < T { len } else { T }] += 1;
+			gaps += 1;
+			len = 0;
+		} else {
+			len += 1;
+		}
+	}
+	let mut expected = [0f64; T + 1];
+	let mut tail = 1.0;
+	for k in 0..T {
+		expected[k] = GAPS as f64 * P * tail;
+		tail *= 1.0 - P;
+	}
+	expected[T] = GAPS as f64 * tail;
+	check("Gap", generator, chi_square_z(chi_square(&observed, &expected), T));
+}
+
+macro_rules! test_generators {
+	($($test:ident),*) => {
+		$(
+			#[test]
+			fn $test() {
+				for seed in [0, 1, 0xDEADBEEF].iter() {
+					super::$test("MSWS", MSWS::new(*seed));
+					super::$test("AtomicRNG", &AtomicRNG::new(*seed));
+					super::$test("CounterRNG", (CounterRNG::new(*seed), 0));
+				}
+			}
+		)*
+	}
+}
+
+mod tests {
+	use super::*;
+	test_generators!(uniformity, serial_correlation, runs, birthday_spacings, gap);
+}
@@ -0,0 +1,215 @@
+// Square root and reciprocal approximations from an integer estimate of the exponent, refined by Newton steps.
+//
+// Halving or negating the bits of a float roughly halves or negates its exponent, and subtracting them from a
+// magic constant turns that into an estimate of 1/sqrt(a) or 1/a. Each Newton step about squares the relative
+// error, until f32 or f64 rounding takes over. The estimates only hold for positive normal numbers,
+// subnormals and non-positive inputs give meaningless results unless noted otherwise.
+//
+// Source: C. Lomont, "Fast Inverse Square Root"
+
+/// Magic constant of the Quake III inverse square root. Max relative error 1.753e-3 after one Newton step.
+pub const QUAKE:u32 = 0x5f3759df;
+/// Lomont's inverse square root constant, optimal for one Newton step, where its max relative error is 1.752e-3.
+pub const LOMONT:u32 = 0x5f375a86;
+/// Lomont's inverse square root constant for f64.
+pub const LOMONT_64:u64 = 0x5fe6ec85e7de30da;
+/// Reciprocal constant, optimal for one Newton step.
+pub const RECIP:u32 = 0x7ef311c2;
+/// Reciprocal constant for f64.
+pub const RECIP_64:u64 = 0x7fde623822fc16e6;
+
+/// Refines an estimate `y` of `1/sqrt(a)`.
+#[inline(always)]
+const fn newton_inverse_sqrt(a:f32, mut y:f32, iterations:u32) -> f32 {
+	let half = 0.5 * a;
+	let mut i = 0;
+	while i < iterations {
+		y = y * (1.5 - half * y * y);
+		i += 1;
+	}
+	return y;
+}
+/// Refines an estimate `y` of `1/a`.
+#[inline(always)]
+const fn newton_recip(a:f32, mut y:f32, iterations:u32) -> f32 {
+	let mut i = 0;
+	while i < iterations {
+		y = y * (2.0 - a * y);
+		i += 1;
+	}
+	return y;
+}
+#[inline(always)]
+const fn newton_inverse_sqrt64(a:f64, mut y:f64, iterations:u32) -> f64 {
+	let half = 0.5 * a;
+	let mut i = 0;
+	while i < iterations {
+		y = y * (1.5 - half * y * y);
+		i += 1;
+	}
+	return y;
+}
+#[inline(always)]
+const fn newton_recip64(a:f64, mut y:f64, iterations:u32) -> f64 {
+	let mut i = 0;
+	while i < iterations {
+		y = y * (2.0 - a * y);
+		i += 1;
+	}
+	return y;
+}
+
+/// Approximates `1/sqrt(a)` from the estimate of a magic constant, like `QUAKE` or `LOMONT`, and `ITERATIONS`
+/// Newton steps.
+#[inline(always)]
+pub const fn inverse_sqrt_magic<const MAGIC:u32, const ITERATIONS:u32>(a:f32) -> f32 {
+	let y = f32::from_bits(MAGIC.wrapping_sub(a.to_bits() >> 1));
+	return newton_inverse_sqrt(a, y, ITERATIONS);
+}
+
+/// Approximates `1/sqrt(a)`, with `ITERATIONS` Newton steps from the `LOMONT` estimate.
+/// Max relative error is 3.44e-2, 1.752e-3, 4.74e-6 and 1.8e-7 from 0 to 3 steps, more steps don't help.
+#[inline(always)]
+pub const fn fast_inverse_sqrt<const ITERATIONS:u32>(a:f32) -> f32 {
+	return inverse_sqrt_magic::<LOMONT, ITERATIONS>(a);
+}
+/// Approximates `sqrt(a)` as `a/sqrt(a)`, with `ITERATIONS` Newton steps from the `LOMONT` estimate.
+/// Max relative error is 3.44e-2, 1.752e-3, 4.77e-6 and 2.1e-7 from 0 to 3 steps, and 0 gives 0.
+#[inline(always)]
+pub const fn fast_sqrt<const ITERATIONS:u32>(a:f32) -> f32 {
+	return a * fast_inverse_sqrt::<ITERATIONS>(a);
+}
+/// Approximates `1/a`, with `ITERATIONS` Newton steps from the `RECIP` estimate.
+/// Max relative error is 5.06e-2, 2.56e-3, 6.7e-6 and 1.5e-7 from 0 to 3 steps, for `|a|` within [2^-126, 2^125].
+/// Negative inputs work too.
+#[inline(always)]
+pub const fn fast_recip<const ITERATIONS:u32>(a:f32) -> f32 {
+	let bits = a.to_bits();
+	let y = f32::from_bits(RECIP.wrapping_sub(bits & 0x7FFFFFFF) | (bits & 0x80000000));
+	return newton_recip(a, y, ITERATIONS);
+}
+
+/// Approximates `1/sqrt(a)`, with `ITERATIONS` Newton steps from the `LOMONT_64` estimate.
+/// Max relative error is 3.43e-2, 1.776e-3, 4.74e-6, 3.36e-11 and 3.8e-16 from 0 to 4 steps.
+#[inline(always)]
+pub const fn fast_inverse_sqrt64<const ITERATIONS:u32>(a:f64) -> f64 {
+	let y = f64::from_bits(LOMONT_64.wrapping_sub(a.to_bits() >> 1));
+	return newton_inverse_sqrt64(a, y, ITERATIONS);
+}
+/// Approximates `sqrt(a)` as `a/sqrt(a)`, with `ITERATIONS` Newton steps from the `LOMONT_64` estimate.
+/// Max relative error is 3.43e-2, 1.776e-3, 4.74e-6, 3.36e-11 and 4.5e-16 from 0 to 4 steps, and 0 gives 0.
+#[inline(always)]
+pub const fn fast_sqrt64<const ITERATIONS:u32>(a:f64) -> f64 {
+	return a * fast_inverse_sqrt64::<ITERATIONS>(a);
+}
+/// Approximates `1/a`, with `ITERATIONS` Newton steps from the `RECIP_64` estimate.
+/// Max relative error is 5.06e-2, 2.56e-3, 6.6e-6, 4.3e-11 and 3.3e-16 from 0 to 4 steps, for `|a|` within
+/// [2^-1022, 2^1021]. Negative inputs work too.
+#[inline(always)]
+pub const fn fast_recip64<const ITERATIONS:u32>(a:f64) -> f64 {
+	let bits = a.to_bits();
+	let y = f64::from_bits(RECIP_64.wrapping_sub(bits & 0x7FFFFFFFFFFFFFFF) | (bits & 0x8000000000000000));
+	return newton_recip64(a, y, ITERATIONS);
+}
+
+/// Approximates `1/sqrt(a)`, with `ITERATIONS` Newton steps from the `rsqrtss` estimate.
+/// Max relative error is 3.3e-4 and 2.8e-7 for 0 and 1 steps, within [1e-37, 1e37].
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+#[inline(always)]
+pub fn fast_inverse_sqrt_sse<const ITERATIONS:u32>(a:f32) -> f32 {
+	return newton_inverse_sqrt(a, arch::inverse_sqrt_estimate(a), ITERATIONS);
+}
+/// Approximates `1/a`, with `ITERATIONS` Newton steps from the `rcpss` estimate.
+/// Max relative error is 3.01e-4 and 2.1e-7 for 0 and 1 steps, for `|a|` within [1e-37, 1e37].
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+#[inline(always)]
+pub fn fast_recip_sse<const ITERATIONS:u32>(a:f32) -> f32 {
+	return newton_recip(a, arch::recip_estimate(a), ITERATIONS);
+}
+
+#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+mod arch {
+	#[cfg(target_arch = "x86")]
+	use core::arch::x86::*;
+	#[cfg(target_arch = "x86_64")]
+	use core::arch::x86_64::*;
+
+	#[inline(always)]
+	pub fn inverse_sqrt_estimate(a:f32) -> f32 {
+		// SAFETY: SSE is enabled for the target.
+		unsafe { _mm_cvtss_f32(_mm_rsqrt_ss(_mm_set_ss(a))) }
+	}
+	#[inline(always)]
+	pub fn recip_estimate(a:f32) -> f32 {
+		// SAFETY: SSE is enabled for the target.
+		unsafe { _mm_cvtss_f32(_mm_rcp_ss(_mm_set_ss(a))) }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::accuracy::ErrorStats;
+
+	// The estimate of 1/a becomes subnormal beyond 2^125 and 2^1021.
+	const RECIP_MAX:f32 = 4.2535296e37;
+	const RECIP_MAX_64:f64 = 2.247116418577895e307;
+
+	fn rel(f:impl FnMut(f32) -> f32, reference:fn(f64) -> f64, to:f32) -> f64 {
+		return ErrorStats::sweep_floats(f, reference, f32::MIN_POSITIVE, to, 997).max_rel;
+	}
+	fn rel64(f:fn(f64) -> f64, reference:fn(f64) -> f64, to:f64) -> f64 {
+		let mut ret = 0f64;
+		let mut bits = f64::MIN_POSITIVE.to_bits();
+		while bits <= to.to_bits() {
+			let a = f64::from_bits(bits);
+			ret = ret.max(((f(a) - reference(a)) / reference(a)).abs());
+			bits += 0x0000_1234_5678_9ABD;
+		}
+		return ret;
+	}
+	fn inverse_sqrt(a:f64) -> f64 { 1.0 / a.sqrt() }
+	fn recip(a:f64) -> f64 { 1.0 / a }
+
+	#[test]
+	fn error_bounds() {
+		assert!(rel(inverse_sqrt_magic::<QUAKE, 1>, inverse_sqrt, f32::MAX) <= 1.753e-3);
+		assert!(rel(fast_inverse_sqrt::<0>, inverse_sqrt, f32::MAX) <= 3.44e-2);
+		assert!(rel(fast_inverse_sqrt::<1>, inverse_sqrt, f32::MAX) <= 1.752e-3);
+		assert!(rel(fast_inverse_sqrt::<2>, inverse_sqrt, f32::MAX) <= 4.74e-6);
+		assert!(rel(fast_inverse_sqrt::<3>, inverse_sqrt, f32::MAX) <= 1.8e-7);
+		assert!(rel(fast_sqrt::<0>, f64::sqrt, f32::MAX) <= 3.44e-2);
+		assert!(rel(fast_sqrt::<1>, f64::sqrt, f32::MAX) <= 1.752e-3);
+		assert!(rel(fast_sqrt::<2>, f64::sqrt, f32::MAX) <= 4.77e-6);
+		assert!(rel(fast_sqrt::<3>, f64::sqrt, f32::MAX) <= 2.1e-7);
+		assert!(rel(fast_recip::<0>, recip, RECIP_MAX) <= 5.06e-2);
+		assert!(rel(fast_recip::<1>, recip, RECIP_MAX) <= 2.56e-3);
+		assert!(rel(fast_recip::<2>, recip, RECIP_MAX) <= 6.7e-6);
+		assert!(rel(fast_recip::<3>, recip, RECIP_MAX) <= 1.5e-7);
+		assert!(rel64(fast_inverse_sqrt64::<0>, inverse_sqrt, f64::MAX) <= 3.43e-2);
+		assert!(rel64(fast_inverse_sqrt64::<1>, inverse_sqrt, f64::MAX) <= 1.776e-3);
+		assert!(rel64(fast_inverse_sqrt64::<2>, inverse_sqrt, f64::MAX) <= 4.74e-6);
+		assert!(rel64(fast_inverse_sqrt64::<3>, inverse_sqrt, f64::MAX) <= 3.36e-11);
+		assert!(rel64(fast_inverse_sqrt64::<4>, inverse_sqrt, f64::MAX) <= 3.8e-16);
+		assert!(rel64(fast_sqrt64::<3>, f64::sqrt, f64::MAX) <= 3.36e-11);
+		assert!(rel64(fast_sqrt64::<4>, f64::sqrt, f64::MAX) <= 4.5e-16);
+		assert!(rel64(fast_recip64::<0>, recip, RECIP_MAX_64) <= 5.06e-2);
+		assert!(rel64(fast_recip64::<1>, recip, RECIP_MAX_64) <= 2.56e-3);
+		assert!(rel64(fast_recip64::<2>, recip, RECIP_MAX_64) <= 6.6e-6);
+		assert!(rel64(fast_recip64::<3>, recip, RECIP_MAX_64) <= 4.3e-11);
+		assert!(rel64(fast_recip64::<4>, recip, RECIP_MAX_64) <= 3.3e-16);
+		assert_eq!((fast_sqrt::<1>(0.0), fast_sqrt64::<1>(0.0)), (0.0, 0.0));
+		assert!((fast_recip::<3>(-4.0) + 0.25).abs() < 1e-7 && (fast_recip64::<4>(-4.0) + 0.25).abs() < 1e-16);
+	}
+
+	#[cfg(all(feature = "simd", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse"))]
+	#[test]
+	fn sse_error_bounds() {
+		// rsqrtss and rcpss flush results beyond the normal range, so the sweep stays within it.
+		let rel = |f:fn(f32) -> f32, reference:fn(f64) -> f64| ErrorStats::sweep_floats(f, reference, 1e-37, 1e37, 997).max_rel;
+		assert!(rel(fast_inverse_sqrt_sse::<0>, inverse_sqrt) <= 3.3e-4);
+		assert!(rel(fast_inverse_sqrt_sse::<1>, inverse_sqrt) <= 2.8e-7);
+		assert!(rel(fast_recip_sse::<0>, recip) <= 3.01e-4);
+		assert!(rel(fast_recip_sse::<1>, recip) <= 2.1e-7);
+	}
+}
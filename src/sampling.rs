@@ -0,0 +1,281 @@
+// Bridson's Poisson-disk sampling
+//
+// Source: https://www.cs.ubc.ca/~rbridson/docs/bridson-siggraph07-poissondisk.pdf
+
+use crate::prng::*;
+use crate::vector::*;
+
+const EMPTY:u32 = u32::MAX;
+
+/// Recommended number of candidates per active point.
+pub const POISSON_ATTEMPTS:u32 = 30;
+
+/// Uniform integer in [0, n).
+#[inline(always)]
+fn rand_below(rng:&mut RNG, n:usize) -> usize {
+	((rng.get_u32() as u64 * n as u64) >> 32) as usize
+}
+
+#[inline(always)]
+fn cell_of(v:f32, cell_size:f32, cells:usize) -> usize {
+	let c = (v / cell_size) as usize;
+	return if c < cells { c } else { cells - 1 };
+}
+
+#[inline(always)]
+fn cells_2d(size:Vec2, radius:f32) -> (f32, usize, usize) {
+	assert!(radius > 0f32, "Poisson-disk radius must be positive");
+	let cell_size = radius / core::f32::consts::SQRT_2;
+	return (cell_size, (size.x / cell_size) as usize + 1, (size.y / cell_size) as usize + 1);
+}
+
+#[inline(always)]
+fn cells_3d(size:Vec3, radius:f32) -> (f32, usize, usize, usize) {
+	assert!(radius > 0f32, "Poisson-disk radius must be positive");
+	const SQRT_3:f32 = 1.7320508;
+	let cell_size = radius / SQRT_3;
+	return (
+		cell_size,
+		(size.x / cell_size) as usize + 1,
+		(size.y / cell_size) as usize + 1,
+		(size.z / cell_size) as usize + 1,
+	);
+}
+
+/// Length of the background grid, required by `poisson_disk_2d`.
+/// Panics if `radius` is not positive.
+pub fn poisson_grid_len_2d(size:Vec2, radius:f32) -> usize {
+	let (_, w, h) = cells_2d(size, radius);
+	return w * h;
+}
+
+/// Length of the background grid, required by `poisson_disk_3d`.
+/// Panics if `radius` is not positive.
+pub fn poisson_grid_len_3d(size:Vec3, radius:f32) -> usize {
+	let (_, w, h, d) = cells_3d(size, radius);
+	return w * h * d;
+}
+
+/// Fills `points` with samples in [0, size], no two of which are closer than `radius`.
+///
+/// `grid` is scratch space of at least `poisson_grid_len_2d(size, radius)` elements.
+/// `attempts` is the number of candidates tried around each point, see `POISSON_ATTEMPTS`.
+/// Returns the number of generated points. Stops early, if `points` is full.
+/// Panics if `radius` is not positive, since the grid would need infinitely many cells.
+pub fn poisson_disk_2d(rng:&mut RNG, size:Vec2, radius:f32, attempts:u32, points:&mut [Vec2], grid:&mut [u32]) -> usize {
+	let (cell_size, w, h) = cells_2d(size, radius);
+	assert!(grid.len() >= w * h, "Poisson-disk grid is too small");
+	let grid = &mut grid[..w * h];
+	for g in grid.iter_mut() { *g = EMPTY; }
+
+	if points.is_empty() {
+		return 0;
+	}
+
+	let cell = |p:&Vec2| cell_of(p.y, cell_size, h) * w + cell_of(p.x, cell_size, w);
+	let radius_sq = radius * radius;
+
+	points[0] = Vec2::new(rng.get_f32() * size.x, rng.get_f32() * size.y);
+	grid[cell(&points[0])] = 0;
+	let mut count = 1;
+	// Points before `inactive` have no room left around them, the rest are active.
+	let mut inactive = 0;
+
+	while inactive < count {
+		let i = inactive + rand_below(rng, count - inactive);
+		let p = points[i];
+		let mut found = false;
+		for _ in 0..attempts {
+			// Uniform in the annulus [radius, 2*radius], by rejection from its bounding square.
+			let offset = loop {
+				let o = Vec2::new(rng.get_f32() * 4f32 - 2f32, rng.get_f32() * 4f32 - 2f32);
+				let len_sq = o.dot(&o);
+				if (1f32..=4f32).contains(&len_sq) {
+					break o * radius;
+				}
+			};
+			let c = p + offset;
+			if c.x < 0f32 || c.y < 0f32 || c.x > size.x || c.y > size.y {
+				continue;
+			}
+			let cx = cell_of(c.x, cell_size, w);
+			let cy = cell_of(c.y, cell_size, h);
+			let mut far = true;
+			'search: for y in cy.saturating_sub(2)..(cy + 3).min(h) {
+				for x in cx.saturating_sub(2)..(cx + 3).min(w) {
+					let g = grid[y * w + x];
+					if g != EMPTY {
+						let d = points[g as usize] - c;
+						if d.dot(&d) < radius_sq {
+							far = false;
+							break 'search;
+						}
+					}
+				}
+			}
+			if far {
+				if count == points.len() {
+					return count;
+				}
+				points[count] = c;
+				grid[cy * w + cx] = count as u32;
+				count += 1;
+				found = true;
+				break;
+			}
+		}
+		if !found {
+			points.swap(i, inactive);
+			grid[cell(&points[i])] = i as u32;
+			grid[cell(&points[inactive])] = inactive as u32;
+			inactive += 1;
+		}
+	}
+
+	return count;
+}
+
+/// Fills `points` with samples in [0, size], no two of which are closer than `radius`.
+///
+/// `grid` is scratch space of at least `poisson_grid_len_3d(size, radius)` elements.
+/// `attempts` is the number of candidates tried around each point, see `POISSON_ATTEMPTS`.
+/// Returns the number of generated points. Stops early, if `points` is full.
+/// Panics if `radius` is not positive, since the grid would need infinitely many cells.
+pub fn poisson_disk_3d(rng:&mut RNG, size:Vec3, radius:f32, attempts:u32, points:&mut [Vec3], grid:&mut [u32]) -> usize {
+	let (cell_size, w, h, d) = cells_3d(size, radius);
+	assert!(grid.len() >= w * h * d, "Poisson-disk grid is too small");
+	let grid = &mut grid[..w * h * d];
+	for g in grid.iter_mut() { *g = EMPTY; }
+
+	if points.is_empty() {
+		return 0;
+	}
+
+	let cell = |p:&Vec3| (cell_of(p.z, cell_size, d) * h + cell_of(p.y, cell_size, h)) * w + cell_of(p.x, cell_size, w);
+	let radius_sq = radius * radius;
+
+	points[0] = Vec3::new(rng.get_f32() * size.x, rng.get_f32() * size.y, rng.get_f32() * size.z);
+	grid[cell(&points[0])] = 0;
+	let mut count = 1;
+	// Points before `inactive` have no room left around them, the rest are active.
+	let mut inactive = 0;
+
+	while inactive < count {
+		let i = inactive + rand_below(rng, count - inactive);
+		let p = points[i];
+		let mut found = false;
+		for _ in 0..attempts {
+			// Uniform in the spherical shell [radius, 2*radius], by rejection from its bounding cube.
+			let offset = loop {
+				let o = Vec3::new(rng.get_f32() * 4f32 - 2f32, rng.get_f32() * 4f32 - 2f32, rng.get_f32() * 4f32 - 2f32);
+				let len_sq = o.dot(&o);
+				if (1f32..=4f32).contains(&len_sq) {
+					break o * radius;
+				}
+			};
+			let c = p + offset;
+			if c.x < 0f32 || c.y < 0f32 || c.z < 0f32 || c.x > size.x || c.y > size.y || c.z > size.z {
+				continue;
+			}
+			let cx = cell_of(c.x, cell_size, w);
+			let cy = cell_of(c.y, cell_size, h);
+			let cz = cell_of(c.z, cell_size, d);
+			let mut far = true;
+			'search: for z in cz.saturating_sub(2)..(cz + 3).min(d) {
+				for y in cy.saturating_sub(2)..(cy + 3).min(h) {
+					for x in cx.saturating_sub(2)..(cx + 3).min(w) {
+						let g = grid[(z * h + y) * w + x];
+						if g != EMPTY {
+							let dist = points[g as usize] - c;
+							if dist.dot(&dist) < radius_sq {
+								far = false;
+								break 'search;
+							}
+						}
+					}
+				}
+			}
+			if far {
+				if count == points.len() {
+					return count;
+				}
+				points[count] = c;
+				grid[(cz * h + cy) * w + cx] = count as u32;
+				count += 1;
+				found = true;
+				break;
+			}
+		}
+		if !found {
+			points.swap(i, inactive);
+			grid[cell(&points[i])] = i as u32;
+			grid[cell(&points[inactive])] = inactive as u32;
+			inactive += 1;
+		}
+	}
+
+	return count;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn poisson_2d_keeps_min_distance() {
+		let size = Vec2::new(10.0, 6.0);
+		let radius = 0.5;
+		let mut rng = RNG::new(1);
+		let mut points = [Vec2::ZERO; 512];
+		let mut grid = [0u32; 1024];
+		assert!(grid.len() >= poisson_grid_len_2d(size, radius));
+		let n = poisson_disk_2d(&mut rng, size, radius, POISSON_ATTEMPTS, &mut points, &mut grid);
+		// Random sequential packing of 0.5 diameter disks fits ~170 of them.
+		assert!(n > 120, "only {} points", n);
+		for i in 0..n {
+			let p = points[i];
+			assert!(p.x >= 0.0 && p.y >= 0.0 && p.x <= size.x && p.y <= size.y);
+			for q in &points[(i + 1)..n] {
+				let d = *q - p;
+				assert!(d.dot(&d) >= radius * radius);
+			}
+		}
+	}
+
+	#[test]
+	fn poisson_3d_keeps_min_distance() {
+		let size = Vec3::new(4.0, 3.0, 2.0);
+		let radius = 0.5;
+		let mut rng = RNG::new(2);
+		let mut points = [Vec3::ZERO; 1024];
+		let mut grid = [0u32; 4096];
+		assert!(grid.len() >= poisson_grid_len_3d(size, radius));
+		let n = poisson_disk_3d(&mut rng, size, radius, POISSON_ATTEMPTS, &mut points, &mut grid);
+		// Random sequential packing of 0.5 diameter spheres fits ~140 of them.
+		assert!(n > 100, "only {} points", n);
+		for i in 0..n {
+			let p = points[i];
+			assert!(p.x >= 0.0 && p.y >= 0.0 && p.z >= 0.0 && p.x <= size.x && p.y <= size.y && p.z <= size.z);
+			for q in &points[(i + 1)..n] {
+				let d = *q - p;
+				assert!(d.dot(&d) >= radius * radius);
+			}
+		}
+	}
+
+	#[test]
+	fn poisson_stops_when_buffer_is_full() {
+		let size = Vec2::new(10.0, 10.0);
+		let mut rng = RNG::new(3);
+		let mut points = [Vec2::ZERO; 16];
+		let mut grid = [0u32; 1024];
+		let n = poisson_disk_2d(&mut rng, size, 0.5, POISSON_ATTEMPTS, &mut points, &mut grid);
+		assert_eq!(n, points.len());
+	}
+
+	#[test]
+	#[should_panic]
+	fn poisson_rejects_zero_radius() {
+		poisson_grid_len_2d(Vec2::new(1.0, 1.0), 0.0);
+	}
+}
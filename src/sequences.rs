@@ -0,0 +1,396 @@
+// Low-discrepancy (quasi-random) sequences
+
+use crate::prng::*;
+use crate::vector::*;
+
+/// Maps the whole `u32` range onto [0.0, 1.0) without rounding up to 1.0.
+#[inline(always)]
+const fn u32_to_unit_f32(v:u32) -> f32 {
+	(v >> 8) as f32 * (1f32 / (1u32 << 24) as f32)
+}
+
+/// Largest `f32` below 1.0.
+const ONE_MINUS_EPS:f32 = 1f32 - f32::EPSILON / 2f32;
+
+const HALTON_MAX_DIGITS:usize = 32;
+/// Serialised size of one `Halton`: the base, followed by all digit shifts.
+const HALTON_STATE_SIZE:usize = 4 + 4 * HALTON_MAX_DIGITS;
+
+/// Halton sequence in an arbitrary base, optionally scrambled.
+///
+/// Scrambling adds a random shift (mod base) to every digit of the radical inverse,
+/// which removes the correlation between sequences with close bases.
+///
+/// Source: https://en.wikipedia.org/wiki/Halton_sequence
+#[derive(Copy, Clone)]
+pub struct Halton {
+	base: u32,
+	/// Number of digits, after which the contribution falls below `f32` precision.
+	digits: u32,
+	shifts: [u32; HALTON_MAX_DIGITS],
+}
+
+impl Halton {
+	pub const fn new(base:u32) -> Halton {
+		assert!(base >= 2, "Halton base must be at least 2");
+		let mut digits = 0;
+		let mut range = 1u64;
+		while range < (1u64 << 24) {
+			range *= base as u64;
+			digits += 1;
+		}
+		Halton {
+			base,
+			digits,
+			shifts: [0; HALTON_MAX_DIGITS],
+		}
+	}
+	pub fn new_scrambled(base:u32, rng:&mut RNG) -> Halton {
+		let mut ret = Halton::new(base);
+		for i in 0..ret.digits as usize {
+			ret.shifts[i] = rng.get_u32() % base;
+		}
+		return ret;
+	}
+	#[inline(always)]
+	pub const fn base(&self) -> u32 {
+		self.base
+	}
+	/// Range of [0.0, 1.0).
+	pub fn get_f32(&self, mut index:u32) -> f32 {
+		let inv_base = 1f64 / self.base as f64;
+		let mut scale = inv_base;
+		let mut ret = 0f64;
+		for i in 0..self.digits as usize {
+			let digit = index % self.base;
+			index /= self.base;
+			// Widened, as the sum overflows `u32` for bases above 2^31.
+			ret += ((digit as u64 + self.shifts[i] as u64) % self.base as u64) as f64 * scale;
+			scale *= inv_base;
+		}
+		let ret = ret as f32;
+		return if ret < 1f32 { ret } else { ONE_MINUS_EPS };
+	}
+	fn write_state(&self, bytes:&mut [u8], at:usize) {
+		write_u32(bytes, at, self.base);
+		for i in 0..HALTON_MAX_DIGITS {
+			write_u32(bytes, at + 4 + 4 * i, self.shifts[i]);
+		}
+	}
+	/// Returns `None` for a base below 2, which `new` rejects.
+	fn read_state(bytes:&[u8], at:usize) -> Option<Halton> {
+		let base = read_u32(bytes, at);
+		if base < 2 {
+			return None;
+		}
+		let mut ret = Halton::new(base);
+		for i in 0..HALTON_MAX_DIGITS {
+			ret.shifts[i] = read_u32(bytes, at + 4 + 4 * i);
+		}
+		return Some(ret);
+	}
+}
+
+/// Two Halton sequences with coprime bases, bases 2 and 3 by default.
+#[derive(Copy, Clone)]
+pub struct Halton2 {
+	pub x: Halton,
+	pub y: Halton,
+}
+
+impl Halton2 {
+	pub const fn new(base_x:u32, base_y:u32) -> Halton2 {
+		Halton2 { x: Halton::new(base_x), y: Halton::new(base_y) }
+	}
+	pub fn new_scrambled(base_x:u32, base_y:u32, rng:&mut RNG) -> Halton2 {
+		Halton2 { x: Halton::new_scrambled(base_x, rng), y: Halton::new_scrambled(base_y, rng) }
+	}
+	pub fn get_vec2(&self, index:u32) -> Vec2 {
+		Vec2::new(self.x.get_f32(index), self.y.get_f32(index))
+	}
+
+	pub const STATE_SIZE:usize = 1 + 2 * HALTON_STATE_SIZE;
+	/// Layout: version byte, followed by the base and the 32 digit shifts of `x` and `y`,
+	/// all as little-endian `u32`s.
+	pub fn to_bytes(&self) -> [u8; Halton2::STATE_SIZE] {
+		let mut ret = [0u8; Halton2::STATE_SIZE];
+		ret[0] = STATE_VERSION;
+		self.x.write_state(&mut ret, 1);
+		self.y.write_state(&mut ret, 1 + HALTON_STATE_SIZE);
+		return ret;
+	}
+	/// Returns `None` if the state was written by an incompatible version or has a base below 2.
+	pub fn from_bytes(bytes:&[u8; Halton2::STATE_SIZE]) -> Option<Halton2> {
+		if bytes[0] != STATE_VERSION {
+			return None;
+		}
+		return Some(Halton2 {
+			x: Halton::read_state(bytes, 1)?,
+			y: Halton::read_state(bytes, 1 + HALTON_STATE_SIZE)?,
+		});
+	}
+}
+
+impl Default for Halton2 {
+	fn default() -> Halton2 {
+		Halton2::new(2, 3)
+	}
+}
+
+/// Three Halton sequences with coprime bases, bases 2, 3 and 5 by default.
+#[derive(Copy, Clone)]
+pub struct Halton3 {
+	pub x: Halton,
+	pub y: Halton,
+	pub z: Halton,
+}
+
+impl Halton3 {
+	pub const fn new(base_x:u32, base_y:u32, base_z:u32) -> Halton3 {
+		Halton3 { x: Halton::new(base_x), y: Halton::new(base_y), z: Halton::new(base_z) }
+	}
+	pub fn new_scrambled(base_x:u32, base_y:u32, base_z:u32, rng:&mut RNG) -> Halton3 {
+		Halton3 {
+			x: Halton::new_scrambled(base_x, rng),
+			y: Halton::new_scrambled(base_y, rng),
+			z: Halton::new_scrambled(base_z, rng),
+		}
+	}
+	pub fn get_vec3(&self, index:u32) -> Vec3 {
+		Vec3::new(self.x.get_f32(index), self.y.get_f32(index), self.z.get_f32(index))
+	}
+
+	pub const STATE_SIZE:usize = 1 + 3 * HALTON_STATE_SIZE;
+	/// Layout: version byte, followed by the base and the 32 digit shifts of `x`, `y` and `z`,
+	/// all as little-endian `u32`s.
+	pub fn to_bytes(&self) -> [u8; Halton3::STATE_SIZE] {
+		let mut ret = [0u8; Halton3::STATE_SIZE];
+		ret[0] = STATE_VERSION;
+		self.x.write_state(&mut ret, 1);
+		self.y.write_state(&mut ret, 1 + HALTON_STATE_SIZE);
+		self.z.write_state(&mut ret, 1 + 2 * HALTON_STATE_SIZE);
+		return ret;
+	}
+	/// Returns `None` if the state was written by an incompatible version or has a base below 2.
+	pub fn from_bytes(bytes:&[u8; Halton3::STATE_SIZE]) -> Option<Halton3> {
+		if bytes[0] != STATE_VERSION {
+			return None;
+		}
+		return Some(Halton3 {
+			x: Halton::read_state(bytes, 1)?,
+			y: Halton::read_state(bytes, 1 + HALTON_STATE_SIZE)?,
+			z: Halton::read_state(bytes, 1 + 2 * HALTON_STATE_SIZE)?,
+		});
+	}
+}
+
+impl Default for Halton3 {
+	fn default() -> Halton3 {
+		Halton3::new(2, 3, 5)
+	}
+}
+
+/// Unscrambled radical inverse of `index` in `base`.
+pub fn halton_f32(base:u32, index:u32) -> f32 {
+	Halton::new(base).get_f32(index)
+}
+
+pub const SOBOL_DIMENSIONS:usize = 14;
+
+/// Primitive polynomials and initial direction numbers by S. Joe and F. Y. Kuo.
+/// The first dimension (van der Corput sequence) is implicit.
+///
+/// Source: https://web.maths.unsw.edu.au/~fkuo/sobol/new-joe-kuo-6.21201
+const SOBOL_INIT:[(u32, u32, [u32; 6]); SOBOL_DIMENSIONS - 1] = [
+	// s, a, m
+	(1,  0, [1, 0, 0, 0,  0,  0]),
+	(2,  1, [1, 3, 0, 0,  0,  0]),
+	(3,  1, [1, 3, 1, 0,  0,  0]),
+	(3,  2, [1, 1, 1, 0,  0,  0]),
+	(4,  1, [1, 1, 3, 3,  0,  0]),
+	(4,  4, [1, 3, 5, 13, 0,  0]),
+	(5,  2, [1, 1, 5, 5,  17, 0]),
+	(5,  4, [1, 1, 5, 5,  5,  0]),
+	(5,  7, [1, 1, 7, 11, 19, 0]),
+	(5, 11, [1, 1, 5, 1,  1,  0]),
+	(5, 13, [1, 1, 1, 3,  11, 0]),
+	(5, 14, [1, 3, 5, 5,  31, 0]),
+	(6,  1, [1, 3, 3, 9,  7,  49]),
+];
+
+const fn sobol_directions() -> [[u32; 32]; SOBOL_DIMENSIONS] {
+	let mut ret = [[0u32; 32]; SOBOL_DIMENSIONS];
+	let mut k = 0;
+	while k < 32 {
+		ret[0][k] = 1 << (31 - k);
+		k += 1;
+	}
+	let mut d = 1;
+	while d < SOBOL_DIMENSIONS {
+		let (s, a, m) = SOBOL_INIT[d - 1];
+		let s = s as usize;
+		let v = &mut ret[d];
+		let mut k = 0;
+		while k < 32 {
+			if k < s {
+				v[k] = m[k] << (31 - k);
+			} else {
+				v[k] = v[k - s] ^ (v[k - s] >> s);
+				let mut i = 1;
+				while i < s {
+					if (a >> (s - 1 - i)) & 1 != 0 {
+						v[k] ^= v[k - i];
+					}
+					i += 1;
+				}
+			}
+			k += 1;
+		}
+		d += 1;
+	}
+	return ret;
+}
+
+const SOBOL_DIRECTIONS:[[u32; 32]; SOBOL_DIMENSIONS] = sobol_directions();
+
+/// Sobol sequence in Gray code order, as a 0.32 fixed point fraction.
+/// `dim` must be less than `SOBOL_DIMENSIONS`.
+pub const fn sobol_u32(dim:usize, index:u32) -> u32 {
+	let v = &SOBOL_DIRECTIONS[dim];
+	let mut gray = index ^ (index >> 1);
+	let mut ret = 0;
+	let mut k = 0;
+	while gray != 0 {
+		if gray & 1 != 0 {
+			ret ^= v[k];
+		}
+		gray >>= 1;
+		k += 1;
+	}
+	return ret;
+}
+/// Range of [0.0, 1.0).
+pub const fn sobol_f32(dim:usize, index:u32) -> f32 {
+	u32_to_unit_f32(sobol_u32(dim, index))
+}
+pub const fn sobol_vec2(index:u32) -> Vec2 {
+	Vec2::new(sobol_f32(0, index), sobol_f32(1, index))
+}
+pub const fn sobol_vec3(index:u32) -> Vec3 {
+	Vec3::new(sobol_f32(0, index), sobol_f32(1, index), sobol_f32(2, index))
+}
+
+// Generalised golden ratios φ_d (x^(d+1) = x + 1), as 0.32 fixed point fractions of 1/φ_d^i.
+// Source: https://extremelearning.com.au/unreasonable-effectiveness-of-quasirandom-sequences/
+const R1_A1:u32 = 0x9E3779B9; // 1/1.61803398874989484820
+const R2_A1:u32 = 0xC13FA9A9; // 1/1.32471795724474602596
+const R2_A2:u32 = 0x91E10DA5; // 1/1.32471795724474602596^2
+const R3_A1:u32 = 0xD1B54A32; // 1/1.22074408460575947536
+const R3_A2:u32 = 0xABC98388; // 1/1.22074408460575947536^2
+const R3_A3:u32 = 0x8CB92BA7; // 1/1.22074408460575947536^3
+const R_OFFSET:u32 = 0x80000000; // 0.5
+
+#[inline(always)]
+const fn r_component(a:u32, index:u32) -> f32 {
+	u32_to_unit_f32(R_OFFSET.wrapping_add(a.wrapping_mul(index)))
+}
+
+/// Additive recurrence with the golden ratio. Range of [0.0, 1.0).
+pub const fn r1_f32(index:u32) -> f32 {
+	r_component(R1_A1, index)
+}
+/// Roberts' R2 additive recurrence, based on the plastic number.
+pub const fn r2_vec2(index:u32) -> Vec2 {
+	Vec2::new(r_component(R2_A1, index), r_component(R2_A2, index))
+}
+/// Roberts' R3 additive recurrence, the 3D generalisation of R2.
+pub const fn r3_vec3(index:u32) -> Vec3 {
+	Vec3::new(r_component(R3_A1, index), r_component(R3_A2, index), r_component(R3_A3, index))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// Every dimension must put exactly one of the first 2^m points in each interval of length 2^-m.
+	#[test]
+	fn sobol_is_stratified() {
+		const M:u32 = 10;
+		for dim in 0..SOBOL_DIMENSIONS {
+			let mut hit = [false; 1 << M];
+			for i in 0..(1u32 << M) {
+				let cell = (sobol_u32(dim, i) >> (32 - M)) as usize;
+				assert!(!hit[cell], "dimension {} is not stratified", dim);
+				hit[cell] = true;
+			}
+		}
+	}
+
+	/// The first two dimensions form a (0,m,2)-net: one point per elementary interval.
+	#[test]
+	fn sobol_2d_is_net() {
+		const M:u32 = 8;
+		for bits_x in 0..=M {
+			let bits_y = M - bits_x;
+			let mut hit = [false; 1 << M];
+			for i in 0..(1u32 << M) {
+				let cx = if bits_x == 0 { 0 } else { sobol_u32(0, i) >> (32 - bits_x) };
+				let cy = if bits_y == 0 { 0 } else { sobol_u32(1, i) >> (32 - bits_y) };
+				let cell = ((cx << bits_y) | cy) as usize;
+				assert!(!hit[cell]);
+				hit[cell] = true;
+			}
+		}
+	}
+
+	#[test]
+	fn halton_radical_inverse() {
+		assert_eq!(halton_f32(2, 0), 0.0);
+		assert_eq!(halton_f32(2, 1), 0.5);
+		assert_eq!(halton_f32(2, 6), 0.375);
+		assert!((halton_f32(3, 5) - 7.0 / 9.0).abs() < 1e-6);
+	}
+
+	#[test]
+	fn scrambled_halton_stays_in_range() {
+		let mut rng = RNG::new(7);
+		let h = Halton3::new_scrambled(2, 3, 5, &mut rng);
+		for i in 0..4096 {
+			let v = h.get_vec3(i);
+			for c in [v.x, v.y, v.z].iter() {
+				assert!(*c >= 0.0 && *c < 1.0);
+			}
+		}
+	}
+
+	#[test]
+	fn scrambled_halton_restore_replays_sequence() {
+		let mut rng = RNG::new(7);
+		let h2 = Halton2::new_scrambled(2, 3, &mut rng);
+		let h3 = Halton3::new_scrambled(2, 3, 5, &mut rng);
+		let restored2 = Halton2::from_bytes(&h2.to_bytes()).unwrap();
+		let restored3 = Halton3::from_bytes(&h3.to_bytes()).unwrap();
+		for i in 0..1000 {
+			let (a, b) = (h2.get_vec2(i), restored2.get_vec2(i));
+			assert_eq!([a.x, a.y], [b.x, b.y]);
+			let (a, b) = (h3.get_vec3(i), restored3.get_vec3(i));
+			assert_eq!([a.x, a.y, a.z], [b.x, b.y, b.z]);
+		}
+
+		let mut state = h2.to_bytes();
+		state[0] = STATE_VERSION.wrapping_add(1);
+		assert!(Halton2::from_bytes(&state).is_none());
+		let mut state = h3.to_bytes();
+		state[1 + HALTON_STATE_SIZE..][..4].copy_from_slice(&1u32.to_le_bytes());
+		assert!(Halton3::from_bytes(&state).is_none());
+	}
+
+	#[test]
+	fn scrambled_halton_large_base() {
+		let mut rng = RNG::new(7);
+		let h = Halton::new_scrambled(u32::MAX, &mut rng);
+		for i in [0, 1, u32::MAX / 2, u32::MAX - 2, u32::MAX - 1].iter() {
+			assert!((0.0..1.0).contains(&h.get_f32(*i)));
+		}
+	}
+}
@@ -5,13 +5,16 @@ pub const PI_SQUARED:f32 = PI * PI;
 pub const TAU:f32 = core::f32::consts::TAU;
 pub const TAU_SQUARED:f32 = TAU * TAU;
 
+use crate::math;
+
+/// Taylor series of cosine up to a^4, max error 2e-2 only within [-PI/2, PI/2].
 pub const fn cos_approx_a1(a:f32) -> f32 {
 	let a2 = a*a;
 	let a4 = a2*a2;
 	return 1.0 - a2/2.0 + a4/24.0;
 }
 
-/// Bhaskara I's sine approximation formula
+/// Bhaskara I's sine approximation formula, max error 1.7e-3 only within [-PI/2, PI/2].
 ///
 /// Source: https://en.wikipedia.org/wiki/Bhaskara_I%27s_sine_approximation_formula
 pub const fn cos_bhaskara_1(a:f32) -> f32 {
@@ -26,6 +29,7 @@ pub const fn cos_bhaskara_1(a:f32) -> f32 {
 		FIVE_PI - 4.0 * a_times_pi_minus_a
 	);
 }
+/// Bhaskara I's formula rewritten for cosine, max error 1.7e-3 only within [-PI/2, PI/2].
 pub const fn cos_bhaskara_2(a:f32) -> f32 {
 	let a2 = a*a;
 	return (
@@ -34,6 +38,7 @@ pub const fn cos_bhaskara_2(a:f32) -> f32 {
 		PI_SQUARED + a2
 	);
 }
+/// Bhaskara I's formula rewritten for cosine, max error 1.7e-3 only within [-PI/2, PI/2].
 pub const fn cos_bhaskara_3(a:f32) -> f32 {
 	let a2 = a*a;
 	return 1.0 - (
@@ -43,21 +48,237 @@ pub const fn cos_bhaskara_3(a:f32) -> f32 {
 	);
 }
 
-pub fn sin32(a:f32) -> f32 {
-	cos32(PI_HALF - a)
+/// Rounding error of PI_HALF, which matters next to the zeros of cos and the poles of tan.
+pub(crate) const PI_HALF_LO:f32 = -4.371139e-8;
+
+/// Bits of 2/PI after the binary point, most significant first.
+/// Preceded by a zero word, so windows may start before the binary point.
+const TWO_OVER_PI:[u32; 9] = [
+	0x00000000,
+	0xA2F9836E, 0x4E441529, 0xFC2757D1, 0xF534DDC0, 0xDB629599, 0x3C439041, 0xFE5163AB, 0xDEBBC561,
+];
+
+/// Largest argument, for which the Cody-Waite reduction is exact.
+pub(crate) const CODY_WAITE_MAX:f32 = 8192.0;
+/// PI/2 split into parts with trailing zero bits, so `n * C1` and `n * C2` are exact while n < 2^13.
+pub(crate) const CODY_WAITE:[f32; 3] = [1.5703125, 4.837512969970703125e-4, 7.54978995489188216e-8];
+
+/// Splits a non-negative `a` into `n * PI/2 + r`, returning `n mod 4` and `r`, which is within [-PI/4, PI/4]
+/// up to rounding. Non-finite `a` gives a NaN remainder.
+///
+/// Source: W. J. Cody, W. Waite, "Software Manual for the Elementary Functions"
+/// Source: M. Payne, R. Hanek, "Radian reduction for trigonometric functions"
+pub(crate) fn reduce_pi_half(a:f32) -> (u32, f32) {
+	const C1:f32 = CODY_WAITE[0];
+	const C2:f32 = CODY_WAITE[1];
+	const C3:f32 = CODY_WAITE[2];
+
+	if !(a <= f32::MAX) {
+		return (0, f32::NAN);
+	}
+	if a <= CODY_WAITE_MAX {
+		let n = (a * core::f32::consts::FRAC_2_PI + 0.5) as u32;
+		let nf = n as f32;
+		return (n & 3, ((a - nf * C1) - nf * C2) - nf * C3);
+	}
+
+	// a = m * 2^k, with 24 bit m. Only the bits of 2/PI from 2^-(k-1) on can affect (a * 2/PI) mod 4,
+	// the earlier ones give multiples of 4. A 96 bit window of them leaves 64 bits for the fraction.
+	let bits = a.to_bits();
+	let m = ((bits & 0x007FFFFF) | 0x00800000) as u128;
+	let k = (bits >> 23) as i32 - 150;
+	let start = (k - 2 + 32) as u32;
+	let word = (start / 32) as usize;
+	let mut window = 0u128;
+	for i in 0..4 {
+		window = (window << 32) | TWO_OVER_PI[word + i] as u128;
+	}
+	let window = (window << (start % 32)) >> 32;
+	let product = m * window; // (a * 2/PI) mod 4, as a fixed point number with 94 fractional bits
+	let fraction = ((product << 34) >> 64) as u64;
+	// Reading the fraction as signed rounds to the nearest quadrant, the upper half belongs to the next one.
+	let quad = ((product >> 94) as u32 + (fraction >> 63) as u32) & 3;
+	const FRACTION_TO_RAD:f32 = PI_HALF / 18446744073709551616.0; // PI/2 * 2^-64
+	return (quad, fraction as i64 as f32 * FRACTION_TO_RAD);
 }
-pub fn cos32(a:f32) -> f32 {
-	let a = a.abs(); // cos(-x) = cos(x)
-//	let a = unsafe { intrinsics::fabsf32(a) };
-	let a = a % PI_TWO; // 0 <= a <= 2*PI
-	let quad = (a / PI_HALF) as u8;
-	return match quad {
-		1 => -cos73s(PI-a),
-		2 => -cos73s(a-PI),
-		3 =>  cos73s(PI_TWO-a),
-		_ =>  cos73s(a),
+
+/// Tangent, max relative error 3.4e-3 (3.2 decimal digits) of `tan(a*PI/4)` only within [-1, 1].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", tan_32s
+pub const fn tan32s(a:f32) -> f32 {
+	const C1:f32 = -3.6112171;
+	const C2:f32 = -4.6133253;
+
+	let a2 = a*a;
+
+	return a * C1 / (C2 + a2);
+}
+/// Tangent, max relative error 2.2e-5 (5.6 decimal digits) of `tan(a*PI/4)` only within [-1, 1].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", tan_56s
+pub const fn tan56s(a:f32) -> f32 {
+	const C1:f32 = -3.16783027;
+	const C2:f32 = 0.134516124;
+	const C3:f32 = -4.033321984;
+
+	let a2 = a*a;
+
+	return a * (C1 + C2 * a2) / (C3 + a2);
+}
+/// Tangent, max relative error 2.8e-7 (8.2 decimal digits, before rounding) of `tan(a*PI/4)` only within [-1, 1].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", tan_82s
+pub const fn tan82s(a:f32) -> f32 {
+	const C1:f32 = 211.849369664121;
+	const C2:f32 = -12.5288887278448;
+	const C3:f32 = 269.7350131214121;
+	const C4:f32 = -71.4145309347748;
+
+	let a2 = a*a;
+
+	return a * (C1 + C2 * a2) / (C3 + a2 * (C4 + a2));
+}
+/// Bits of 2/PI after the binary point for reducing `f64` arguments, most significant first.
+/// Preceded by a zero word, so windows may start before the binary point.
+const TWO_OVER_PI_64:[u64; 21] = [
+	0x0000000000000000,
+	0xA2F9836E4E441529, 0xFC2757D1F534DDC0, 0xDB6295993C439041, 0xFE5163ABDEBBC561,
+	0xB7246E3A424DD2E0, 0x06492EEA09D1921C, 0xFE1DEB1CB129A73E, 0xE88235F52EBB4484,
+	0xE99C7026B45F7E41, 0x3991D639835339F4, 0x9C845F8BBDF9283B, 0x1FF897FFDE05980F,
+	0xEF2F118B5A0A6D1F, 0x6D367ECF27CB09B7, 0x4F463F669E5FEA2D, 0x7527BAC7EBE5F17B,
+	0x3D0739F78A5292EA, 0x6BFB5FB11F8D5D08, 0x56033046FC7B6BAB, 0xF0CFBC209AF4361D,
+];
+
+/// Largest argument, for which the `f64` Cody-Waite reduction is exact.
+const CODY_WAITE_MAX_64:f64 = 524288.0;
+
+/// `f64` version of `reduce_pi_half`.
+fn reduce_pi_half_64(a:f64) -> (u32, f64) {
+	// PI/2 split into 33 bit parts, so `n * C1`, `n * C2` and `n * C3` are exact while n < 2^20.
+	const C1:f64 = 1.57079632673412561417e+00;
+	const C2:f64 = 6.07710050630396597660e-11;
+	const C3:f64 = 2.02226624871116645580e-21;
+
+	if !(a <= f64::MAX) {
+		return (0, f64::NAN);
+	}
+	if a <= CODY_WAITE_MAX_64 {
+		let n = (a * core::f64::consts::FRAC_2_PI + 0.5) as u64;
+		let nf = n as f64;
+		return ((n & 3) as u32, ((a - nf * C1) - nf * C2) - nf * C3);
+	}
+
+	// a = m * 2^k, with 53 bit m. As for f32, only the bits of 2/PI from 2^-(k-1) on matter. A 192 bit window
+	// of them leaves 128 bits for the fraction, which covers the worst cancellation of 61 bits.
+	let bits = a.to_bits();
+	let m = ((bits & 0x000FFFFFFFFFFFFF) | 0x0010000000000000) as u128;
+	let k = (bits >> 52) as i32 - 1075;
+	let start = (k - 2 + 64) as u32;
+	let word = (start / 64) as usize;
+	let mut window = [0u128; 3];
+	for i in 0..3 {
+		let pair = (TWO_OVER_PI_64[word + i] as u128) << 64 | TWO_OVER_PI_64[word + i + 1] as u128;
+		window[i] = ((pair << (start % 64)) >> 64) as u64 as u128;
+	}
+	// m * window in 64 bit limbs, a fixed point number with 190 fractional bits, of which we keep 128.
+	let low = m * window[2];
+	let mid = m * window[1] + (low >> 64);
+	let high = m * window[0] + (mid >> 64);
+	let fraction = (high << 66) | ((mid as u64 as u128) << 2) | ((low as u64) >> 62) as u128;
+	// Reading the fraction as signed rounds to the nearest quadrant, the upper half belongs to the next one.
+	let quad = ((high >> 62) as u32 + (fraction >> 127) as u32) & 3;
+	const FRACTION_TO_RAD:f64 = core::f64::consts::FRAC_PI_2 / 340282366920938463463374607431768211456.0; // PI/2 * 2^-128
+	return (quad, fraction as i128 as f64 * FRACTION_TO_RAD);
+}
+
+/// Sine, max error 1.2e-16 only within [-PI/4, PI/4].
+///
+/// Source: Sun Microsystems, fdlibm, k_sin.c
+pub const fn sin_poly64(a:f64) -> f64 {
+	const S1:f64 = -1.66666666666666324348e-01;
+	const S2:f64 =  8.33333333332248946124e-03;
+	const S3:f64 = -1.98412698298579493134e-04;
+	const S4:f64 =  2.75573137070700676789e-06;
+	const S5:f64 = -2.50507602534068634195e-08;
+	const S6:f64 =  1.58969099521155010221e-10;
+
+	let a2 = a*a;
+
+	return a + a * a2 * (S1 + a2 * (S2 + a2 * (S3 + a2 * (S4 + a2 * (S5 + a2 * S6)))));
+}
+/// Cosine, max error 1.2e-16 only within [-PI/4, PI/4].
+///
+/// Source: Sun Microsystems, fdlibm, k_cos.c
+pub const fn cos_poly64(a:f64) -> f64 {
+	const C1:f64 =  4.16666666666666019037e-02;
+	const C2:f64 = -1.38888888888741095749e-03;
+	const C3:f64 =  2.48015872894767294178e-05;
+	const C4:f64 = -2.75573143513906633035e-07;
+	const C5:f64 =  2.08757232129817482790e-09;
+	const C6:f64 = -1.13596475577881948265e-11;
+
+	let a2 = a*a;
+
+	return 1.0 - 0.5 * a2 + a2 * a2 * (C1 + a2 * (C2 + a2 * (C3 + a2 * (C4 + a2 * (C5 + a2 * C6)))));
+}
+
+/// `f64` version of `sincos_quadrant`.
+#[inline(always)]
+fn sincos_quadrant_64(quad:u32, r:f64) -> (f64, f64) {
+	let s = sin_poly64(r);
+	let c = cos_poly64(r);
+	return match quad & 3 {
+		0 => ( s,  c),
+		1 => ( c, -s),
+		2 => (-s, -c),
+		_ => (-c,  s),
 	};
 }
+
+/// Sine, max error 2.3e-16 for any finite input.
+pub fn sin64(a:f64) -> f64 {
+	return sincos64(a).0;
+}
+/// Cosine, max error 2.3e-16 for any finite input.
+pub fn cos64(a:f64) -> f64 {
+	let (quad, r) = reduce_pi_half_64(if a < 0.0 { -a } else { a }); // cos(-x) = cos(x)
+	return sincos_quadrant_64(quad, r).1;
+}
+/// Sine and cosine of the same angle, sharing a single range reduction. Max error 2.3e-16 for any finite input.
+pub fn sincos64(a:f64) -> (f64, f64) {
+	let (quad, r) = reduce_pi_half_64(if a < 0.0 { -a } else { a }); // sin(-x) = -sin(x), cos(-x) = cos(x)
+	let (sin, cos) = sincos_quadrant_64(quad, r);
+	return (if a.is_sign_negative() { -sin } else { sin }, cos);
+}
+
+/// Trigonometry for generic code over `f32` and `f64`, backed by `sincos32` and `sincos64`.
+pub trait FastTrig : Sized {
+	fn fast_sin(self) -> Self;
+	fn fast_cos(self) -> Self;
+	fn fast_sincos(self) -> (Self, Self);
+}
+
+impl FastTrig for f32 {
+	#[inline(always)]
+	fn fast_sin(self) -> f32 { sin32(self) }
+	#[inline(always)]
+	fn fast_cos(self) -> f32 { cos32(self) }
+	#[inline(always)]
+	fn fast_sincos(self) -> (f32, f32) { sincos32(self) }
+}
+
+impl FastTrig for f64 {
+	#[inline(always)]
+	fn fast_sin(self) -> f64 { sin64(self) }
+	#[inline(always)]
+	fn fast_cos(self) -> f64 { cos64(self) }
+	#[inline(always)]
+	fn fast_sincos(self) -> (f64, f64) { sincos64(self) }
+}
+
+/// Cosine, max error 6e-4 (3.2 decimal digits) only within [-PI/2, PI/2].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", cos_32s
 pub const fn cos32s(a:f32) -> f32 {
 	const C1:f32 =  0.99940307;
 	const C2:f32 = -0.49558072;
@@ -67,6 +288,9 @@ pub const fn cos32s(a:f32) -> f32 {
 
 	return C1 + a2 * (C2 + a2 * C3);
 }
+/// Cosine, max error 6.9e-6 (5.2 decimal digits) only within [-PI/2, PI/2].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", cos_52s
 pub const fn cos52s(a:f32) -> f32 {
 	const C1:f32 =  0.9999932946;
 	const C2:f32 = -0.4999124376;
@@ -77,18 +301,374 @@ pub const fn cos52s(a:f32) -> f32 {
 
 	return C1 + a2 * (C2 + a2 * (C3 + a2 * C4));
 }
+/// Cosine, max error 1.7e-7 (7.3 decimal digits) only within [-PI/2, PI/2].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", cos_73s
 pub const fn cos73s(a:f32) -> f32 {
 	const C1:f32 =  0.999999953464;
-	const C2:f32 = -0.4999999053455;
+	const C2:f32 = -0.499999053455;
 	const C3:f32 =  0.0416635846769;
 	const C4:f32 = -0.0013853704264;
-	const C5:f32 =  0.000023233;
+	const C5:f32 =  0.00002315393167;
 
 	let a2 = a*a;
 
 	return C1 + a2 * (C2 + a2 * (C3 + a2 * (C4 + a2 * C5)));
 }
 
+/// Sine, max relative error 5.7e-4 only within [-PI/4, PI/4].
+///
+/// Minimax fit of `a + a^3 * p(a^2)`, which returns tiny arguments unchanged, like the other `sin_minimax` kernels.
+pub const fn sin_minimax2(a:f32) -> f32 {
+	const C1:f32 = -0.16243109053;
+
+	return a + a * (a*a) * C1;
+}
+/// Sine, max relative error 1.93e-6 only within [-PI/4, PI/4].
+pub const fn sin_minimax3(a:f32) -> f32 {
+	const C1:f32 = -0.16663391918;
+	const C2:f32 =  0.00816332114;
+
+	let a2 = a*a;
+
+	return a + a * a2 * (C1 + a2 * C2);
+}
+/// Coefficients of `sin_minimax4`, shared with the vectorised slice functions.
+pub(crate) const SIN_MINIMAX4:[f32; 3] = [-0.166666546121, 0.00833216093018, -0.000195153064641];
+/// Sine, max relative error 7.3e-8 (0.79 ulp) only within [-PI/4, PI/4].
+pub const fn sin_minimax4(a:f32) -> f32 {
+	const C1:f32 = SIN_MINIMAX4[0];
+	const C2:f32 = SIN_MINIMAX4[1];
+	const C3:f32 = SIN_MINIMAX4[2];
+
+	let a2 = a*a;
+
+	return a + a * a2 * (C1 + a2 * (C2 + a2 * C3));
+}
+/// Coefficients of `cos_minimax5`, shared with the vectorised slice functions.
+pub(crate) const COS_MINIMAX5:[f32; 4] = [-0.5, 0.0416666468677, -0.00138873675814, 0.0000244384591034];
+/// Cosine, max error 6.8e-8 (1.14 ulp) only within [-PI/4, PI/4].
+///
+/// Minimax fit of `1 - a^2/2 + a^4 * p(a^2)`, which returns exactly 1 for 0, unlike `cos73s`.
+pub const fn cos_minimax5(a:f32) -> f32 {
+	const C1:f32 = COS_MINIMAX5[0];
+	const C2:f32 = COS_MINIMAX5[1];
+	const C3:f32 = COS_MINIMAX5[2];
+	const C4:f32 = COS_MINIMAX5[3];
+
+	let a2 = a*a;
+
+	return 1.0 + a2 * (C1 + a2 * (C2 + a2 * (C3 + a2 * C4)));
+}
+
+/// Arctangent, max error 1.1e-7 (6.6 decimal digits) only within [-tan(PI/12), tan(PI/12)].
+///
+/// Source: J. Ganssle, "A Guide to Approximations", atan_66s
+pub const fn atan66s(a:f32) -> f32 {
+	const C1:f32 = 1.6867629106;
+	const C2:f32 = 0.4378497304;
+	const C3:f32 = 1.6867633134;
+
+	let a2 = a*a;
+
+	return a * (C1 + a2 * C2) / (C3 + a2);
+}
+/// Arctangent, max error 1.6e-3 only within [-1, 1].
+///
+/// Source: S. Rajan, S. Wang, R. Inkol, A. Joyal, "Efficient approximations for the arctangent function"
+pub const fn atan_rajan(a:f32) -> f32 {
+	const C1:f32 = 0.2447;
+	const C2:f32 = 0.0663;
+
+	let abs = if a < 0.0 { -a } else { a };
+
+	return (PI / 4.0) * a - a * (abs - 1.0) * (C1 + C2 * abs);
+}
+/// Arctangent, max error 1.2e-5 only within [-1, 1].
+///
+/// Source: M. Abramowitz, I. Stegun, "Handbook of Mathematical Functions", 4.4.49
+pub const fn atan_as49(a:f32) -> f32 {
+	const C1:f32 =  0.9998660;
+	const C2:f32 = -0.3302995;
+	const C3:f32 =  0.1801410;
+	const C4:f32 = -0.0851330;
+	const C5:f32 =  0.0208351;
+
+	let a2 = a*a;
+
+	return a * (C1 + a2 * (C2 + a2 * (C3 + a2 * (C4 + a2 * C5))));
+}
+/// Arctangent, max error 1.7e-7 only within [0, 1]. Reduces to `atan66s`.
+pub fn atan_reduced66s(a:f32) -> f32 {
+	const TAN_PI_6:f32 = 0.57735026918962576451;
+	const TAN_PI_12:f32 = 0.26794919243112270647;
+	const PI_6:f32 = PI / 6.0;
+
+	if a > TAN_PI_12 {
+		return PI_6 + atan66s((a - TAN_PI_6) / (1.0 + TAN_PI_6 * a)); // atan(x) = PI/6 + atan((x - tan(PI/6)) / (1 + tan(PI/6) * x))
+	}
+	return atan66s(a);
+}
+/// Arccosine, max error 6.8e-5 only within [0, 1].
+///
+/// Source: M. Abramowitz, I. Stegun, "Handbook of Mathematical Functions", 4.4.45
+pub fn acos_as45(a:f32) -> f32 {
+	const C0:f32 =  1.5707288;
+	const C1:f32 = -0.2121144;
+	const C2:f32 =  0.0742610;
+	const C3:f32 = -0.0187293;
+
+	return math::sqrt(1.0 - a) * (C0 + a * (C1 + a * (C2 + a * C3)));
+}
+/// Arccosine, max error 5.1e-6 only within [0, 1].
+///
+/// Same form as `acos_as45` with one more coefficient, fitted for the least max error.
+pub fn acos_minimax5(a:f32) -> f32 {
+	const C0:f32 =  1.5707915255;
+	const C1:f32 = -0.2142804234;
+	const C2:f32 =  0.0856374918;
+	const C3:f32 = -0.0376167984;
+	const C4:f32 =  0.0097322469;
+
+	return math::sqrt(1.0 - a) * (C0 + a * (C1 + a * (C2 + a * (C3 + a * C4))));
+}
+/// Arccosine, max error 2.6e-7 only within [0, 1].
+///
+/// Source: M. Abramowitz, I. Stegun, "Handbook of Mathematical Functions", 4.4.46
+pub fn acos_as46(a:f32) -> f32 {
+	const C0:f32 =  1.5707963050;
+	const C1:f32 = -0.2145988016;
+	const C2:f32 =  0.0889789874;
+	const C3:f32 = -0.0501743046;
+	const C4:f32 =  0.0308918810;
+	const C5:f32 = -0.0170881256;
+	const C6:f32 =  0.0066700901;
+	const C7:f32 = -0.0012624911;
+
+	return math::sqrt(1.0 - a) * (C0 + a * (C1 + a * (C2 + a * (C3 + a * (C4 + a * (C5 + a * (C6 + a * C7)))))));
+}
+
+/// Speed versus accuracy of the trig functions, chosen once by picking one of the marker types
+/// `Fast`, `Medium` or `Precise`, whose docs list the max errors.
+/// ```
+/// use tiny_lib::trig::*;
+/// type Trig = Medium;
+/// let (sin, cos) = Trig::sincos(1.0);
+/// assert!((Trig::atan2(sin, cos) - 1.0).abs() < 1e-4);
+/// ```
+pub trait Precision {
+	/// Sine, only within [-PI/4, PI/4].
+	fn sin_kernel(a:f32) -> f32;
+	/// Cosine, only within [-PI/4, PI/4].
+	fn cos_kernel(a:f32) -> f32;
+	/// `tan(a*PI/4)`, only within [-1, 1].
+	fn tan_kernel(a:f32) -> f32;
+	/// Arctangent, only within [0, 1].
+	fn atan_kernel(a:f32) -> f32;
+	/// Arccosine, only within [0, 1].
+	fn acos_kernel(a:f32) -> f32;
+
+	/// Sine and cosine of `quad * PI/2 + r`, with `r` within [-PI/4, PI/4].
+	#[inline(always)]
+	fn sincos_quadrant(quad:u32, r:f32) -> (f32, f32) {
+		let s = Self::sin_kernel(r);
+		let c = Self::cos_kernel(r);
+		return match quad & 3 {
+			0 => ( s,  c),
+			1 => ( c, -s),
+			2 => (-s, -c),
+			_ => (-c,  s),
+		};
+	}
+
+	/// Sine of any finite input.
+	fn sin(a:f32) -> f32 {
+		return Self::sincos(a).0;
+	}
+	/// Cosine of any finite input.
+	fn cos(a:f32) -> f32 {
+		let (quad, r) = reduce_pi_half(math::abs(a)); // cos(-x) = cos(x)
+		return Self::sincos_quadrant(quad, r).1;
+	}
+	/// Sine and cosine of the same angle, sharing a single range reduction.
+	fn sincos(a:f32) -> (f32, f32) {
+		let (quad, r) = reduce_pi_half(math::abs(a)); // sin(-x) = -sin(x), cos(-x) = cos(x)
+		let (sin, cos) = Self::sincos_quadrant(quad, r);
+		return (if a.is_sign_negative() { -sin } else { sin }, cos);
+	}
+	/// Sine and cosine of a binary angle, where `u32` wraps around once per full turn.
+	///
+	/// The reduction is exact, so this is also a cheaper alternative to `sincos`.
+	fn sincos_bam32(a:u32) -> (f32, f32) {
+		const BAM_TO_RAD:f32 = TAU / 4294967296.0; // TAU * 2^-32
+		let quad = a.wrapping_add(1 << 29) >> 30; // nearest quarter turn
+		let r = a.wrapping_sub(quad << 30) as i32 as f32 * BAM_TO_RAD;
+		return Self::sincos_quadrant(quad, r);
+	}
+	/// Tangent of any finite input, the max error is relative.
+	fn tan(a:f32) -> f32 {
+		const FOUR_OVER_PI:f32 = 4.0 / PI;
+
+		// tan(-x) = -tan(x). Near the poles tan(x) ~ -1/r, so r needs to be accurate relative to itself,
+		// which the f32 Cody-Waite reduction isn't. The f64 one is, and beyond it the Payne-Hanek one is too.
+		let abs = math::abs(a);
+		let (quad, r) = if abs <= CODY_WAITE_MAX {
+			let (quad, r) = reduce_pi_half_64(abs as f64);
+			(quad, r as f32)
+		} else {
+			reduce_pi_half(abs)
+		};
+		let tan = Self::tan_kernel(r * FOUR_OVER_PI);
+		let ret = if quad & 1 == 0 { tan } else { -1.0 / tan }; // tan(x + PI/2) = -1/tan(x)
+		return if a.is_sign_negative() { -ret } else { ret };
+	}
+	/// Arctangent over the whole range.
+	fn atan(a:f32) -> f32 {
+		let negative = a < 0.0;
+		let a = if negative { -a } else { a }; // atan(-x) = -atan(x)
+		let ret = if a > 1.0 {
+			PI_HALF - Self::atan_kernel(1.0 / a) // atan(x) = PI/2 - atan(1/x)
+		} else {
+			Self::atan_kernel(a)
+		};
+		return if negative { -ret } else { ret };
+	}
+	/// Angle of the vector (x, y), in the range [-PI, PI].
+	///
+	/// Like `f32::atan2`, signed zeros pick the side, so `atan2(0.0, -0.0)` is PI and `atan2(-0.0, -1.0)` is -PI,
+	/// and two infinities give the diagonals.
+	fn atan2(y:f32, x:f32) -> f32 {
+		let abs_y = math::abs(y); // atan2(-y, x) = -atan2(y, x)
+		let ret = if abs_y == f32::INFINITY && math::abs(x) == f32::INFINITY {
+			if x > 0.0 { PI_HALF * 0.5 } else { PI - PI_HALF * 0.5 }
+		} else if abs_y == 0.0 && x == 0.0 {
+			if x.is_sign_negative() { PI } else { 0.0 }
+		} else if x.is_sign_negative() {
+			PI - Self::atan(abs_y / -x) // atan2(y, x) = PI - atan2(y, -x)
+		} else {
+			Self::atan(abs_y / x)
+		};
+		return if y.is_sign_negative() { -ret } else { ret };
+	}
+	/// Arccosine within [-1, 1].
+	fn acos(a:f32) -> f32 {
+		let negative = a < 0.0;
+		let a = if negative { -a } else { a }; // acos(-x) = PI - acos(x)
+		let ret = Self::acos_kernel(a);
+		return if negative { PI - ret } else { ret };
+	}
+	/// Arcsine within [-1, 1].
+	fn asin(a:f32) -> f32 {
+		return PI_HALF - Self::acos(a);
+	}
+}
+
+/// Cheapest kernels. Max errors: 6e-4 for sin and cos, 3.4e-3 relative for tan, 1.6e-3 for atan and atan2,
+/// 6.8e-5 for acos and asin.
+pub struct Fast;
+/// Max errors: 6.9e-6 for sin and cos, 2.2e-5 relative for tan, 1.2e-5 for atan and atan2, 5.1e-6 for acos and asin.
+pub struct Medium;
+/// Most accurate kernels, used by `sin32` and the other free functions. Max errors: 1.2e-7 for sin and cos,
+/// 4.6e-7 relative for tan, 2.3e-7 for atan, 3.1e-7 for atan2, 4.4e-7 for acos and asin.
+/// The free functions list their errors in ulp too, where they are bounded.
+pub struct Precise;
+
+impl Precision for Fast {
+	#[inline(always)]
+	fn sin_kernel(a:f32) -> f32 { sin_minimax2(a) }
+	#[inline(always)]
+	fn cos_kernel(a:f32) -> f32 { cos32s(a) }
+	#[inline(always)]
+	fn tan_kernel(a:f32) -> f32 { tan32s(a) }
+	#[inline(always)]
+	fn atan_kernel(a:f32) -> f32 { atan_rajan(a) }
+	#[inline(always)]
+	fn acos_kernel(a:f32) -> f32 { acos_as45(a) }
+}
+
+impl Precision for Medium {
+	#[inline(always)]
+	fn sin_kernel(a:f32) -> f32 { sin_minimax3(a) }
+	#[inline(always)]
+	fn cos_kernel(a:f32) -> f32 { cos52s(a) }
+	#[inline(always)]
+	fn tan_kernel(a:f32) -> f32 { tan56s(a) }
+	#[inline(always)]
+	fn atan_kernel(a:f32) -> f32 { atan_as49(a) }
+	#[inline(always)]
+	fn acos_kernel(a:f32) -> f32 { acos_minimax5(a) }
+}
+
+impl Precision for Precise {
+	#[inline(always)]
+	fn sin_kernel(a:f32) -> f32 { sin_minimax4(a) }
+	#[inline(always)]
+	fn cos_kernel(a:f32) -> f32 { cos_minimax5(a) }
+	#[inline(always)]
+	fn tan_kernel(a:f32) -> f32 { tan82s(a) }
+	#[inline(always)]
+	fn atan_kernel(a:f32) -> f32 { atan_reduced66s(a) }
+	#[inline(always)]
+	fn acos_kernel(a:f32) -> f32 { acos_as46(a) }
+}
+
+/// Sine, max error 1.2e-7 for any finite input.
+pub fn sin32(a:f32) -> f32 {
+	return Precise::sin(a);
+}
+/// Cosine, max error 1.2e-7 for any finite input.
+pub fn cos32(a:f32) -> f32 {
+	return Precise::cos(a);
+}
+/// Sine and cosine of the same angle, sharing a single range reduction. Max error 1.2e-7 for any finite input.
+pub fn sincos32(a:f32) -> (f32, f32) {
+	return Precise::sincos(a);
+}
+/// Sine and cosine of a binary angle, where `u32` wraps around once per full turn. Max error 2.6e-7.
+///
+/// The reduction is exact, so this is also a cheaper alternative to `sincos32`.
+pub fn sincos32_bam32(a:u32) -> (f32, f32) {
+	return Precise::sincos_bam32(a);
+}
+/// Sine of a binary angle, where `u32` wraps around once per full turn. Max error 2.6e-7.
+pub fn sin32_bam32(a:u32) -> f32 {
+	return sincos32_bam32(a).0;
+}
+/// Cosine of a binary angle, where `u32` wraps around once per full turn. Max error 2.6e-7.
+pub fn cos32_bam32(a:u32) -> f32 {
+	return sincos32_bam32(a).1;
+}
+/// Sine of a binary angle, where `u16` wraps around once per full turn. Max error 2.6e-7.
+pub fn sin32_bam16(a:u16) -> f32 {
+	return sincos32_bam32((a as u32) << 16).0;
+}
+/// Cosine of a binary angle, where `u16` wraps around once per full turn. Max error 2.6e-7.
+pub fn cos32_bam16(a:u16) -> f32 {
+	return sincos32_bam32((a as u32) << 16).1;
+}
+/// Tangent, max relative error 4.6e-7 for any finite normal input, 6.8 ulp within (-PI/2, PI/2).
+pub fn tan32(a:f32) -> f32 {
+	return Precise::tan(a);
+}
+/// Arctangent over the whole range, max error 2.3e-7 or 6.6 ulp.
+pub fn atan32(a:f32) -> f32 {
+	return Precise::atan(a);
+}
+/// Angle of the vector (x, y), in the range [-PI, PI], max error 3.1e-7. Signed zeros and infinities give the
+/// same results as `f32::atan2`.
+pub fn atan2_32(y:f32, x:f32) -> f32 {
+	return Precise::atan2(y, x);
+}
+/// Arccosine within [-1, 1], max error 4.4e-7 or 2.9 ulp.
+pub fn acos32(a:f32) -> f32 {
+	return Precise::acos(a);
+}
+/// Arcsine within [-1, 1], max error 4.4e-7. 6 ulp within |a| >= 0.5, unbounded in ulp towards 0.
+pub fn asin32(a:f32) -> f32 {
+	return Precise::asin(a);
+}
+
+#[cfg(all(test, feature = "bench"))]
 mod benchmarks {
 	extern crate test;
 	use test::Bencher;
@@ -114,4 +694,254 @@ mod benchmarks {
 		}
 		return ret;
 	}
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::accuracy::ErrorStats;
+
+	/// Max absolute error of `f` against the `f64` reference over `steps` points of [from, to].
+	fn max_error(f:impl Fn(f32) -> f32, reference:impl Fn(f64) -> f64, from:f32, to:f32, steps:u32) -> f64 {
+		return ErrorStats::sweep(f, reference, from, to, steps).max_abs;
+	}
+
+	/// Max relative error of `f` against the `f64` reference over `steps` points of [from, to].
+	fn max_rel_error(f:impl Fn(f32) -> f32, reference:impl Fn(f64) -> f64, from:f32, to:f32, steps:u32) -> f64 {
+		return ErrorStats::sweep(f, reference, from, to, steps).max_rel;
+	}
+
+	#[test]
+	fn published_kernel_error_bounds() {
+		use core::f64::consts::FRAC_PI_4;
+		let tan = |a:f64| (a * FRAC_PI_4).tan();
+		assert!(max_error(cos_approx_a1 , f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 2e-2);
+		assert!(max_error(cos_bhaskara_1, f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 1.7e-3);
+		assert!(max_error(cos_bhaskara_2, f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 1.7e-3);
+		assert!(max_error(cos_bhaskara_3, f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 1.7e-3);
+		assert!(max_error(cos32s        , f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 6e-4);
+		assert!(max_error(cos52s        , f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 6.9e-6);
+		assert!(max_error(cos73s        , f64::cos, -PI_HALF, PI_HALF, 1_000_000) <= 1.7e-7);
+		assert!(max_error(cos_minimax5  , f64::cos, -PI_HALF * 0.5, PI_HALF * 0.5, 1_000_000) <= 6.8e-8);
+		assert!(max_rel_error(sin_minimax2, f64::sin, -PI_HALF * 0.5, PI_HALF * 0.5, 1_000_000) <= 5.7e-4);
+		assert!(max_rel_error(sin_minimax3, f64::sin, -PI_HALF * 0.5, PI_HALF * 0.5, 1_000_000) <= 1.93e-6);
+		assert!(max_rel_error(sin_minimax4, f64::sin, -PI_HALF * 0.5, PI_HALF * 0.5, 1_000_000) <= 7.3e-8);
+		assert!(max_rel_error(tan32s, tan, -1.0, 1.0, 1_000_000) <= 3.4e-3);
+		assert!(max_rel_error(tan56s, tan, -1.0, 1.0, 1_000_000) <= 2.2e-5);
+		assert!(max_rel_error(tan82s, tan, -1.0, 1.0, 1_000_000) <= 2.8e-7);
+		assert!(max_error(atan66s        , f64::atan, -0.26794919, 0.26794919, 1_000_000) <= 1.1e-7);
+		assert!(max_error(atan_rajan     , f64::atan, -1.0, 1.0, 1_000_000) <= 1.6e-3);
+		assert!(max_error(atan_as49      , f64::atan, -1.0, 1.0, 1_000_000) <= 1.2e-5);
+		assert!(max_error(atan_reduced66s, f64::atan,  0.0, 1.0, 1_000_000) <= 1.7e-7);
+		assert!(max_error(acos_as45      , f64::acos,  0.0, 1.0, 1_000_000) <= 6.8e-5);
+		assert!(max_error(acos_as46      , f64::acos,  0.0, 1.0, 1_000_000) <= 2.6e-7);
+		assert!(max_error(acos_minimax5  , f64::acos,  0.0, 1.0, 1_000_000) <= 5.1e-6);
+	}
+
+	/// sin(a) rounds to `a` for tiny arguments, which the odd kernels return unchanged, signed zeros included.
+	#[test]
+	fn sin_of_tiny_arguments_is_exact() {
+		for a in [0.0, -0.0, 1e-10, -1e-10, 1e-30, -1e-30, f32::MIN_POSITIVE, 1e-45].iter() {
+			assert_eq!(sin32(*a).to_bits(), a.to_bits());
+			assert_eq!(Fast::sin(*a).to_bits(), a.to_bits());
+			assert_eq!(Medium::sin(*a).to_bits(), a.to_bits());
+			assert_eq!(sincos32(*a), (*a, 1.0));
+		}
+		assert_eq!(cos32(0.0), 1.0);
+	}
+
+	#[test]
+	fn sincos_and_tan_error_bounds() {
+		let mut sin_err = 0f64;
+		let mut cos_err = 0f64;
+		let mut tan_err = 0f64;
+		for i in -100_000..=100_000 {
+			let a = i as f32 * 0.0001;
+			let (sin, cos) = sincos32(a);
+			sin_err = sin_err.max((sin as f64 - (a as f64).sin()).abs());
+			cos_err = cos_err.max((cos as f64 - (a as f64).cos()).abs());
+			let b = i as f32 * 0.0000157; // (-PI/2, PI/2)
+			if b != 0.0 {
+				let tan = (b as f64).tan();
+				tan_err = tan_err.max(((tan32(b) as f64 - tan) / tan).abs());
+			}
+		}
+		assert!(sin_err <= 1.2e-7);
+		assert!(cos_err <= 1.2e-7);
+		assert!(tan_err <= 4.6e-7);
+	}
+
+	#[test]
+	fn large_argument_reduction() {
+		// Every 4999th float from 1 to f32::MAX covers both reductions and the switch between them,
+		// and every float around the worst case of each function, found exhaustively, covers that.
+		let sweep = |f:fn(f32) -> f32, reference:fn(f64) -> f64, worst:f32| {
+			let (from, to) = (f32::from_bits(worst.to_bits() - 1000), f32::from_bits(worst.to_bits() + 1000));
+			return ErrorStats::sweep_floats(f, reference, 1.0, f32::MAX, 4999)
+				.merge(ErrorStats::sweep_floats(f, reference, -f32::MAX, -1.0, 4999))
+				.merge(ErrorStats::sweep_floats(f, reference, from, to, 1));
+		};
+		let sin = sweep(sin32, f64::sin, 8.203727e27);
+		let cos = sweep(cos32, f64::cos, 1.2078159e17);
+		let tan = sweep(tan32, f64::tan, 1.1725129e25);
+		assert!(sin.max_abs <= 1.2e-7 && sin.max_abs > 1.18e-7, "{:?}", sin);
+		assert!(cos.max_abs <= 1.2e-7 && cos.max_abs > 1.17e-7, "{:?}", cos);
+		// Relative over the whole range, poles included.
+		assert!(tan.max_rel <= 4.6e-7 && tan.max_rel > 4.5e-7, "{:?}", tan);
+		let mut bits = 1f32.to_bits();
+		while bits < f32::MAX.to_bits() {
+			for a in [f32::from_bits(bits), -f32::from_bits(bits)] {
+				assert_eq!(sincos32(a), (sin32(a), cos32(a)));
+			}
+			bits += 4999;
+		}
+		assert!(sin32(f32::INFINITY).is_nan() && cos32(f32::NEG_INFINITY).is_nan() && tan32(f32::NAN).is_nan());
+	}
+
+	#[test]
+	fn binary_angle_error_bounds() {
+		let mut err = 0f64;
+		for i in 0..=u16::MAX {
+			let a = i as f64 * (core::f64::consts::TAU / 65536.0);
+			err = err.max((sin32_bam16(i) as f64 - a.sin()).abs()).max((cos32_bam16(i) as f64 - a.cos()).abs());
+			let j = (i as u32) << 16 | (i as u32).wrapping_mul(40503);
+			let a = j as f64 * (core::f64::consts::TAU / 4294967296.0);
+			err = err.max((sin32_bam32(j) as f64 - a.sin()).abs()).max((cos32_bam32(j) as f64 - a.cos()).abs());
+		}
+		assert!(err <= 2.6e-7, "{}", err);
+		assert_eq!(sincos32_bam32(0), (0.0, 1.0));
+		assert_eq!(sin32_bam16(0x4000), 1.0);
+	}
+
+	#[test]
+	fn f64_error_bounds() {
+		let mut err = 0f64;
+		let mut check = |a:f64| {
+			let (sin, cos) = sincos64(a);
+			assert_eq!((sin, cos), (sin64(a), cos64(a)));
+			err = err.max((sin - a.sin()).abs()).max((cos - a.cos()).abs());
+		};
+		for i in -1_000_000..=1_000_000 {
+			check(i as f64 * 0.00001);
+		}
+		// Every 2^40+1th double from 1 to f64::MAX covers both reductions and the switch between them.
+		let mut bits = 1f64.to_bits();
+		while bits < f64::MAX.to_bits() {
+			check(f64::from_bits(bits));
+			check(-f64::from_bits(bits));
+			bits += (1 << 40) + 1;
+		}
+		check(CODY_WAITE_MAX_64);
+		check(f64::MAX);
+		let mut poly_err = 0f64;
+		for i in -1_000_000..=1_000_000 {
+			let a = i as f64 * (core::f64::consts::FRAC_PI_4 / 1e6);
+			poly_err = poly_err.max((sin_poly64(a) - a.sin()).abs()).max((cos_poly64(a) - a.cos()).abs());
+		}
+		assert!(poly_err <= 1.2e-16, "{}", poly_err);
+		assert!(err <= 2.3e-16, "{}", err);
+		assert!(cos64(f64::INFINITY).is_nan() && sin64(f64::NAN).is_nan());
+	}
+
+	#[test]
+	fn fast_trig_is_generic() {
+		fn rotate<F:FastTrig + Copy + core::ops::Mul<Output = F> + core::ops::Sub<Output = F>>(x:F, y:F, a:F) -> F {
+			let (sin, cos) = a.fast_sincos();
+			return x * cos - y * sin;
+		}
+		assert_eq!(rotate(1f32, 0f32, 0.5), cos32(0.5));
+		assert_eq!(rotate(1f64, 0f64, 0.5), cos64(0.5));
+		assert_eq!(0.5f32.fast_cos(), cos32(0.5));
+		assert_eq!(0.5f64.fast_sin(), sin64(0.5));
+	}
+
+	/// Max errors of a precision tier: sin and cos, relative tan, atan, atan2, acos and asin.
+	fn tier_errors<P:Precision>() -> [f64; 5] {
+		let mut ret = [0f64; 5];
+		for i in -200_000..=200_000 {
+			let a = i as f32 * 0.00005; // [-10, 10]
+			let (sin, cos) = P::sincos(a);
+			assert_eq!((sin, cos), (P::sin(a), P::cos(a)));
+			ret[0] = ret[0].max((sin as f64 - (a as f64).sin()).abs()).max((cos as f64 - (a as f64).cos()).abs());
+			let b = i as f32 * 0.00000785; // (-PI/2, PI/2)
+			if b != 0.0 {
+				let tan = (b as f64).tan();
+				ret[1] = ret[1].max(((P::tan(b) as f64 - tan) / tan).abs());
+			}
+			let c = i as f32 * 0.0005; // [-100, 100]
+			ret[2] = ret[2].max((P::atan(c) as f64 - (c as f64).atan()).abs());
+			let angle = i as f64 / 200_000.0 * core::f64::consts::PI;
+			let (y, x) = ((angle.sin() * 3.0) as f32, (angle.cos() * 3.0) as f32);
+			ret[3] = ret[3].max((P::atan2(y, x) as f64 - (y as f64).atan2(x as f64)).abs());
+			let d = i as f32 / 200_000.0; // [-1, 1]
+			ret[4] = ret[4].max((P::acos(d) as f64 - (d as f64).acos()).abs()).max((P::asin(d) as f64 - (d as f64).asin()).abs());
+		}
+		return ret;
+	}
+
+	#[test]
+	fn precision_tier_error_bounds() {
+		let fast = tier_errors::<Fast>();
+		let medium = tier_errors::<Medium>();
+		let precise = tier_errors::<Precise>();
+		let bounds = [
+			[6e-4  , 3.4e-3, 1.6e-3, 1.6e-3, 6.8e-5],
+			[6.9e-6, 2.2e-5, 1.2e-5, 1.2e-5, 5.1e-6],
+			[1.2e-7, 4.6e-7, 2e-7  , 3.1e-7, 4.2e-7],
+		];
+		for (errors, bounds) in [fast, medium, precise].iter().zip(bounds.iter()) {
+			for (error, bound) in errors.iter().zip(bounds.iter()) {
+				assert!(error <= bound, "{:?} exceeds {:?}", errors, bounds);
+			}
+		}
+	}
+
+	#[test]
+	fn inverse_trig_error_bounds() {
+		assert!(max_error(atan32 , f64::atan, -100.0, 100.0, 1_000_000) <= 2e-7);
+		assert!(max_error(acos32 , f64::acos, -1.0  , 1.0  , 1_000_000) <= 4.1e-7);
+		assert!(max_error(asin32 , f64::asin, -1.0  , 1.0  , 1_000_000) <= 4.1e-7);
+		let mut err = 0f64;
+		for i in 0..=100_000 {
+			let angle = (i as f64 / 100_000.0 * 2.0 - 1.0) * core::f64::consts::PI;
+			let (y, x) = (angle.sin() * 3.0, angle.cos() * 3.0);
+			let e = (atan2_32(y as f32, x as f32) as f64 - (y as f32 as f64).atan2(x as f32 as f64)).abs();
+			if e > err { err = e; }
+		}
+		assert!(err <= 3e-7);
+	}
+
+	#[test]
+	fn atan2_special_values() {
+		let inf = f32::INFINITY;
+		let values = [0.0, -0.0, 1.0, -1.0, 1e-40, -1e-40, 3e38, -3e38, inf, -inf];
+		for &y in values.iter() {
+			for &x in values.iter() {
+				let (ret, expected) = (atan2_32(y, x), y.atan2(x));
+				assert!(ret.to_bits() == expected.to_bits() || (ret - expected).abs() <= 3e-7, "atan2({}, {}) = {}, not {}", y, x, ret, expected);
+			}
+		}
+		assert_eq!(atan2_32(0.0, -0.0), PI);
+		assert_eq!(atan2_32(-0.0, -1.0), -PI);
+		assert_eq!(atan2_32(inf, inf), PI_HALF * 0.5);
+		assert_eq!(atan2_32(-inf, -inf), -(PI - PI_HALF * 0.5));
+		assert!(atan2_32(f32::NAN, 1.0).is_nan() && atan2_32(1.0, f32::NAN).is_nan());
+		for &(y, x) in [(0.0, -0.0), (-0.0, -1.0), (inf, inf), (-inf, 2.0)].iter() {
+			assert_eq!(Fast::atan2(y, x), atan2_32(y, x));
+			assert_eq!(Medium::atan2(y, x), atan2_32(y, x));
+		}
+	}
+
+	/// Measured exhaustively, asserted on every 97th float to keep the test fast.
+	#[test]
+	fn published_ulp_bounds() {
+		let ulp = |f:fn(f32) -> f32, reference:fn(f64) -> f64, from:f32, to:f32| ErrorStats::sweep_floats(f, reference, from, to, 97).max_ulp;
+		let below_pi_half = f32::from_bits(PI_HALF.to_bits() - 1);
+		assert!(ulp(tan32 , f64::tan , -below_pi_half, below_pi_half) <= 6.8);
+		assert!(ulp(atan32, f64::atan, -f32::MAX     , f32::MAX     ) <= 6.6);
+		assert!(ulp(acos32, f64::acos, -1.0          , 1.0          ) <= 2.9);
+		assert!(ulp(asin32, f64::asin, -1.0          , -0.5         ) <= 6.0);
+		assert!(ulp(asin32, f64::asin, 0.5           , 1.0          ) <= 6.0);
+	}
+}
+
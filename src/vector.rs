@@ -1,59 +1,303 @@
 use core::ops::*;
 use crate::util::*;
+use crate::math;
+use crate::math::Float;
 
-pub trait Vec : Add + Sub + Mul<f32> + AddAssign + SubAssign + MulAssign<f32> + Copy + Clone {
+/// Float vectors, with `f32` or `f64` components.
+#[allow(clippy::len_without_is_empty)] // `len` is the Euclidean length, not a number of elements
+pub trait Vec : Add + Sub + Mul<<Self as Vec>::Scalar> + AddAssign + SubAssign + MulAssign<<Self as Vec>::Scalar> + Copy + Clone {
+	type Scalar : Copy;
 	const ZERO:Self;
 	const HALF:Self;
 	const ONE:Self;
-	fn dot(&self, v2:&Self) -> f32;
-	fn len(&self) -> f32;
-	fn inv_len(&self) -> f32;
+	fn dot(&self, v2:&Self) -> Self::Scalar;
+	fn len(&self) -> Self::Scalar;
+	fn inv_len(&self) -> Self::Scalar;
 	fn normalize(&self) -> Self;
 	fn normalize2(&self) -> Self;
 }
 
+/// One component-wise operator `$o`, for every mix of vectors and references, with `$t` scalars on either side.
+macro_rules! impl_vec_op {
+	(
+		$t:ty; $vec:ident; ( $($var:ident),+ ); $op:ident, $f:ident, $o:tt; $op_assign:ident, $f_assign:ident, $o_assign:tt
+	) => {
+		impl $op<&$vec> for &$vec {
+			type Output = $vec;
+			#[inline(always)]
+			fn $f(self, rhs: &$vec) -> $vec {
+				$vec {
+					$($var: self.$var $o rhs.$var),+
+				}
+			}
+		}
+		impl $op<$t> for &$vec {
+			type Output = $vec;
+			#[inline(always)]
+			fn $f(self, rhs: $t) -> $vec {
+				$vec {
+					$($var: self.$var $o rhs),+
+				}
+			}
+		}
+		impl $op<&$vec> for $t {
+			type Output = $vec;
+			#[inline(always)]
+			fn $f(self, rhs: &$vec) -> $vec {
+				$vec {
+					$($var: self $o rhs.$var),+
+				}
+			}
+		}
+		impl $op< $vec> for  $vec { type Output = $vec; #[inline(always)] fn $f(self, rhs: $vec) -> $vec { &self $o &rhs } }
+		impl $op<&$vec> for  $vec { type Output = $vec; #[inline(always)] fn $f(self, rhs:&$vec) -> $vec { &self $o  rhs } }
+		impl $op< $vec> for &$vec { type Output = $vec; #[inline(always)] fn $f(self, rhs: $vec) -> $vec {  self $o &rhs } }
+		impl $op<$t> for $vec { type Output = $vec; #[inline(always)] fn $f(self, rhs:$t) -> $vec { &self $o rhs } }
+		impl $op<$vec> for $t { type Output = $vec; #[inline(always)] fn $f(self, rhs:$vec) -> $vec { self $o &rhs } }
+		impl $op_assign<&$vec> for $vec {
+			#[inline(always)]
+			fn $f_assign(&mut self, rhs: &$vec) {
+				$(self.$var $o_assign rhs.$var;)+
+			}
+		}
+		impl $op_assign<$t> for $vec {
+			#[inline(always)]
+			fn $f_assign(&mut self, rhs: $t) {
+				$(self.$var $o_assign rhs;)+
+			}
+		}
+		impl $op_assign<$vec> for $vec { #[inline(always)] fn $f_assign(&mut self, rhs:$vec) { *self $o_assign &rhs; } }
+	};
+}
+
+/// Comparison results of vectors, one `bool` per component.
+macro_rules! impl_bvec {
+	(
+		$($bvec:ident; ( $($var:ident),+ ));+
+	) => {
+		$(
+			#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+			pub struct $bvec {
+				$(pub $var: bool),+
+			}
+			impl $bvec {
+				#[inline(always)]
+				pub const fn new($($var: bool),+) -> $bvec {
+					$bvec {
+						$($var),+
+					}
+				}
+				/// Whether any component is true.
+				#[inline(always)]
+				pub const fn any(&self) -> bool {
+					false $(|| self.$var)+
+				}
+				/// Whether all components are true.
+				#[inline(always)]
+				pub const fn all(&self) -> bool {
+					true $(&& self.$var)+
+				}
+			}
+			impl Not for $bvec {
+				type Output = $bvec;
+				#[inline(always)]
+				fn not(self) -> $bvec {
+					$bvec {
+						$($var: !self.$var),+
+					}
+				}
+			}
+		)+
+	};
+}
+impl_bvec!(
+	BVec1; (x);
+	BVec2; (x,y);
+	BVec3; (x,y,z);
+	BVec4; (x,y,z,w)
+);
+
+/// Component-wise comparison of `self` and `v2` by `$o`, into a mask.
+macro_rules! impl_vec_cmp {
+	(
+		$vec:ident, $bvec:ident; ( $($var:ident),+ ); $f:ident, $o:tt
+	) => {
+		impl $vec {
+			#[inline(always)]
+			pub fn $f(&self, v2:&$vec) -> $bvec {
+				$bvec {
+					$($var: self.$var $o v2.$var),+
+				}
+			}
+		}
+	};
+}
+
+/// Constructor, arithmetic and component-wise utilities shared by the float and integer vectors, with `$t`
+/// components and `$bvec` masks. `%` is the remainder of `$t`, which takes the sign of the left side.
+macro_rules! impl_vec_ops {
+	(
+		$t:ty; $vec:ident, $bvec:ident; ( $($var:ident),+ )
+	) => {
+		impl $vec {
+			#[inline(always)]
+			pub const fn new($($var: $t),+) -> $vec {
+				$vec {
+					$($var),+
+				}
+			}
+			/// Smaller of each pair of components. If one of them is NaN, the component of `self` is kept.
+			#[inline(always)]
+			pub fn min(&self, v2:&$vec) -> $vec {
+				$vec {
+					$($var: if v2.$var < self.$var { v2.$var } else { self.$var }),+
+				}
+			}
+			/// Larger of each pair of components. If one of them is NaN, the component of `self` is kept.
+			#[inline(always)]
+			pub fn max(&self, v2:&$vec) -> $vec {
+				$vec {
+					$($var: if v2.$var > self.$var { v2.$var } else { self.$var }),+
+				}
+			}
+			/// Each component limited to [min, max], which must not be reversed.
+			#[inline(always)]
+			pub fn clamp(&self, min:&$vec, max:&$vec) -> $vec {
+				return self.max(min).min(max);
+			}
+			/// Smallest component, NaN components are skipped unless they come first.
+			#[inline(always)]
+			pub fn min_element(&self) -> $t {
+				let mut ret = self.x;
+				$(ret = if self.$var < ret { self.$var } else { ret };)+
+				return ret;
+			}
+			/// Largest component, NaN components are skipped unless they come first.
+			#[inline(always)]
+			pub fn max_element(&self) -> $t {
+				let mut ret = self.x;
+				$(ret = if self.$var > ret { self.$var } else { ret };)+
+				return ret;
+			}
+			/// Components of `if_true` where `mask` is set, and of `if_false` elsewhere.
+			#[inline(always)]
+			pub fn select(mask:&$bvec, if_true:&$vec, if_false:&$vec) -> $vec {
+				$vec {
+					$($var: if mask.$var { if_true.$var } else { if_false.$var }),+
+				}
+			}
+		}
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmpeq, ==);
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmpne, !=);
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmplt, <);
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmple, <=);
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmpgt, >);
+		impl_vec_cmp!($vec, $bvec; ($($var),+); cmpge, >=);
+		impl_vec_op!($t; $vec; ($($var),+); Add, add, +; AddAssign, add_assign, +=);
+		impl_vec_op!($t; $vec; ($($var),+); Sub, sub, -; SubAssign, sub_assign, -=);
+		impl_vec_op!($t; $vec; ($($var),+); Mul, mul, *; MulAssign, mul_assign, *=);
+		impl_vec_op!($t; $vec; ($($var),+); Div, div, /; DivAssign, div_assign, /=);
+		impl_vec_op!($t; $vec; ($($var),+); Rem, rem, %; RemAssign, rem_assign, %=);
+	};
+}
+
+/// `Neg` of vectors with signed components.
+macro_rules! impl_vec_neg {
+	(
+		$vec:ident; ( $($var:ident),+ )
+	) => {
+		impl Neg for &$vec {
+			type Output = $vec;
+			#[inline(always)]
+			fn neg(self) -> $vec {
+				$vec {
+					$($var: -self.$var),+
+				}
+			}
+		}
+		impl Neg for $vec { type Output = $vec; #[inline(always)] fn neg(self) -> $vec { -&self } }
+	};
+}
+
+/// Float vectors with `$t` components, whose square root is `$sqrt`.
 macro_rules! impl_vec {
 	(
-    $($vec:ident; ( $($var:ident),+ ));+
+    $t:ty, $sqrt:path; $($vec:ident, $bvec:ident; ( $($var:ident),+ ));+
   ) => {
   	$(
 			#[derive(Copy, Clone)]
 			pub struct $vec {
-				$(pub $var: f32),+
+				$(pub $var: $t),+
 			}
+			impl_vec_ops!($t; $vec, $bvec; ($($var),+));
+			impl_vec_neg!($vec; ($($var),+));
 			impl $vec {
 				#[inline(always)]
-				pub const fn new($($var: f32),+) -> $vec {
+				pub fn abs(&self) -> $vec {
 					$vec {
-						$($var),+
+						$($var: Float::abs(self.$var)),+
+					}
+				}
+				/// 1 with the sign of each component, so -1 for -0.0, or NaN.
+				#[inline(always)]
+				pub fn signum(&self) -> $vec {
+					$vec {
+						$($var: Float::signum(self.$var)),+
+					}
+				}
+				#[inline(always)]
+				pub fn floor(&self) -> $vec {
+					$vec {
+						$($var: Float::floor(self.$var)),+
+					}
+				}
+				#[inline(always)]
+				pub fn ceil(&self) -> $vec {
+					$vec {
+						$($var: Float::ceil(self.$var)),+
+					}
+				}
+				/// Rounds each component to the nearest integer, halves away from zero.
+				#[inline(always)]
+				pub fn round(&self) -> $vec {
+					$vec {
+						$($var: Float::round(self.$var)),+
+					}
+				}
+				/// `self - self.floor()` like in GLSL, which is never negative, unlike `f32::fract`.
+				#[inline(always)]
+				pub fn fract(&self) -> $vec {
+					$vec {
+						$($var: self.$var - Float::floor(self.$var)),+
 					}
 				}
 			}
 			impl Vec for $vec {
+				type Scalar = $t;
 				const ZERO:$vec = $vec{
-					$($var: 0f32),+
+					$($var: 0.0),+
 				};
 				const HALF:$vec = $vec{
-					$($var: 0.5f32),+
+					$($var: 0.5),+
 				};
 				const ONE:$vec = $vec{
-					$($var: 1f32),+
+					$($var: 1.0),+
 				};
 				#[inline(always)]
-				fn dot(&self, v2:&$vec) -> f32 {
-					0f32 $(
+				fn dot(&self, v2:&$vec) -> $t {
+					0.0 $(
 						+ (self.$var * v2.$var)
 					)+
 				}
 				#[inline(always)]
-				fn len(&self) -> f32 {
-					(0f32 $(
+				fn len(&self) -> $t {
+					$sqrt(0.0 $(
 						+ (self.$var * self.$var)
-					)+).sqrt()
+					)+)
 				}
 				#[inline(always)]
-				fn inv_len(&self) -> f32 {
-					(0f32 $(
+				fn inv_len(&self) -> $t {
+					(0.0 $(
 						+ (self.$var * self.$var)
 					)+).quick_inverse_sqrt()
 				}
@@ -72,108 +316,193 @@ macro_rules! impl_vec {
 					}
 				}
 			}
-			impl Add<&$vec> for &$vec {
+			impl Lerp<$t> for &$vec {
 				type Output = $vec;
 				#[inline(always)]
-				fn add(self, rhs: &$vec) -> $vec {
+				fn lerp(self, v2:&$vec, t:$t) -> $vec {
 					$vec {
-						$($var: self.$var + rhs.$var),+
+						$($var: <$t>::lerp(self.$var, v2.$var, t)),+
 					}
 				}
-			}
-			impl Sub<&$vec> for &$vec {
-				type Output = $vec;
 				#[inline(always)]
-				fn sub(self, rhs: &$vec) -> $vec {
+				fn lerp_p(self, v2:&$vec, t:$t) -> $vec {
 					$vec {
-						$($var: self.$var - rhs.$var),+
+						$($var: <$t>::lerp_p(self.$var, v2.$var, t)),+
 					}
 				}
 			}
-			impl Add<$vec> for $vec { type Output = $vec; #[inline(always)] fn add(self, rhs: $vec) -> $vec { &self + &rhs } }
-			impl Sub<$vec> for $vec { type Output = $vec; #[inline(always)] fn sub(self, rhs: $vec) -> $vec { &self - &rhs } }
-			impl Add<f32> for $vec {
+			impl Lerp<$t> for $vec {
 				type Output = $vec;
+				#[inline(always)] fn lerp  (self, v2:$vec, t:$t) -> $vec { (&self).lerp  (&v2, t) }
+				#[inline(always)] fn lerp_p(self, v2:$vec, t:$t) -> $vec { (&self).lerp_p(&v2, t) }
+			}
+    )+
+  }
+}
+impl_vec!(
+	f32, math::sqrt;
+	Vec1, BVec1; (x);
+	Vec2, BVec2; (x,y);
+	Vec3, BVec3; (x,y,z);
+	Vec4, BVec4; (x,y,z,w)
+);
+impl_vec!(
+	f64, math::sqrt64;
+	DVec1, BVec1; (x);
+	DVec2, BVec2; (x,y);
+	DVec3, BVec3; (x,y,z);
+	DVec4, BVec4; (x,y,z,w)
+);
+
+/// Conversions between `f32` vectors and `f64` vectors, lossless towards `f64` and rounding towards `f32`.
+macro_rules! impl_vec_f64 {
+	(
+		$($vec:ident, $dvec:ident; ( $($var:ident),+ ));+
+	) => {
+		$(
+			impl From<$vec> for $dvec {
 				#[inline(always)]
-				fn add(self, rhs: f32) -> $vec {
-					$vec {
-						$($var: self.$var + rhs),+
+				fn from(v:$vec) -> $dvec {
+					$dvec {
+						$($var: v.$var as f64),+
 					}
 				}
 			}
-			impl Add<$vec> for f32 {
-				type Output = $vec;
+			impl $dvec {
+				/// Nearest `f32` vector. Components beyond the range of `f32` become infinite.
 				#[inline(always)]
-				fn add(self, rhs: $vec) -> $vec { rhs + self }
+				pub fn to_f32(&self) -> $vec {
+					$vec {
+						$($var: self.$var as f32),+
+					}
+				}
 			}
-			impl Mul<f32> for $vec {
-				type Output = $vec;
+		)+
+	};
+}
+impl_vec_f64!(
+	Vec1, DVec1; (x);
+	Vec2, DVec2; (x,y);
+	Vec3, DVec3; (x,y,z);
+	Vec4, DVec4; (x,y,z,w)
+);
+
+/// Integer vectors with `$t` components, which convert from and to the float vector `$fvec`.
+/// Distances are in the unsigned `$u`, since they can exceed the range of a signed `$t`.
+macro_rules! impl_int_vec {
+	(
+		$t:ty, $u:ty; $($vec:ident, $fvec:ident, $bvec:ident; ( $($var:ident),+ ));+
+	) => {
+		$(
+			#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+			pub struct $vec {
+				$(pub $var: $t),+
+			}
+			impl_vec_ops!($t; $vec, $bvec; ($($var),+));
+			impl $vec {
+				pub const ZERO:$vec = $vec { $($var: 0),+ };
+				pub const ONE:$vec = $vec { $($var: 1),+ };
+
+				#[inline(always)]
+				pub fn dot(&self, v2:&$vec) -> $t {
+					0 $(+ self.$var * v2.$var)+
+				}
+				/// Sum of the distances along each axis, the number of steps between grid cells without diagonals.
+				#[inline(always)]
+				pub fn manhattan(&self, v2:&$vec) -> $u {
+					0 $(+ self.$var.abs_diff(v2.$var))+
+				}
+				/// Largest distance along any axis, the number of steps between grid cells with diagonals.
+				#[inline(always)]
+				pub fn chebyshev(&self, v2:&$vec) -> $u {
+					let mut ret = 0;
+					$(ret = ret.max(self.$var.abs_diff(v2.$var));)+
+					return ret;
+				}
+
+				/// Rounds each component down. Out of range components saturate, NaN gives 0.
 				#[inline(always)]
-				fn mul(self, rhs: f32) -> $vec {
+				pub fn from_floor(v:&$fvec) -> $vec {
 					$vec {
-						$($var: self.$var * rhs),+
+						$($var: Float::floor(v.$var) as $t),+
 					}
 				}
-			}
-			impl Mul<$vec> for f32 {
-				type Output = $vec;
+				/// Rounds each component to the nearest integer, halves away from zero.
+				/// Out of range components saturate, NaN gives 0.
 				#[inline(always)]
-				fn mul(self, rhs: $vec) -> $vec { rhs * self }
-			}
-			impl Div<$vec> for $vec {
-				type Output = $vec;
-				fn div(self, rhs: $vec) -> $vec {
+				pub fn from_round(v:&$fvec) -> $vec {
 					$vec {
-						$($var: self.$var / rhs.$var),+
+						$($var: Float::round(v.$var) as $t),+
 					}
 				}
-			}
-			impl AddAssign for $vec {
+				/// Rounds each component towards zero. Out of range components saturate, NaN gives 0.
 				#[inline(always)]
-				fn add_assign(&mut self, rhs: $vec) {
-					$(self.$var += rhs.$var;)+
+				pub fn from_trunc(v:&$fvec) -> $vec {
+					$vec {
+						$($var: v.$var as $t),+
+					}
 				}
-			}
-			impl SubAssign for $vec {
+				/// Nearest float vector, exact up to 2^24.
 				#[inline(always)]
-				fn sub_assign(&mut self, rhs: $vec) {
-					$(self.$var -= rhs.$var;)+
+				pub fn to_f32(&self) -> $fvec {
+					$fvec {
+						$($var: self.$var as f32),+
+					}
 				}
 			}
-			impl MulAssign<f32> for $vec {
+			impl From<$vec> for $fvec {
 				#[inline(always)]
-				fn mul_assign(&mut self, rhs: f32) {
-					$(self.$var *= rhs;)+
-				}
+				fn from(v:$vec) -> $fvec { v.to_f32() }
 			}
-			impl Lerp for &$vec {
-				type Output = $vec;
+		)+
+	};
+}
+impl_int_vec!(
+	i32, u32;
+	IVec2, Vec2, BVec2; (x,y);
+	IVec3, Vec3, BVec3; (x,y,z)
+);
+impl_int_vec!(
+	u32, u32;
+	UVec2, Vec2, BVec2; (x,y);
+	UVec3, Vec3, BVec3; (x,y,z)
+);
+impl_int_vec!(
+	i16, u16;
+	I16Vec2, Vec2, BVec2; (x,y);
+	I16Vec3, Vec3, BVec3; (x,y,z)
+);
+/// `Neg`, `abs` and `signum` of integer vectors with signed components.
+macro_rules! impl_int_vec_signed {
+	(
+		$($vec:ident; ( $($var:ident),+ ));+
+	) => {
+		$(
+			impl_vec_neg!($vec; ($($var),+));
+			impl $vec {
+				/// Absolute value of each component, which overflows for the minimum like the scalar `abs`.
 				#[inline(always)]
-				fn lerp(self, v2:&$vec, t:f32) -> $vec {
+				pub fn abs(&self) -> $vec {
 					$vec {
-						$($var: f32::lerp(self.$var, v2.$var, t)),+
+						$($var: self.$var.abs()),+
 					}
 				}
+				/// -1, 0 or 1 by the sign of each component.
 				#[inline(always)]
-				fn lerp_p(self, v2:&$vec, t:f32) -> $vec {
+				pub fn signum(&self) -> $vec {
 					$vec {
-						$($var: f32::lerp_p(self.$var, v2.$var, t)),+
+						$($var: self.$var.signum()),+
 					}
 				}
 			}
-			impl Lerp for $vec {
-				type Output = $vec;
-				#[inline(always)] fn lerp  (self, v2:$vec, t:f32) -> $vec { (&self).lerp  (&v2, t) }
-				#[inline(always)] fn lerp_p(self, v2:$vec, t:f32) -> $vec { (&self).lerp_p(&v2, t) }
-			}
-    )+
-  }
+		)+
+	};
 }
-impl_vec!(
-	Vec1; (x);
-	Vec2; (x,y);
-	Vec3; (x,y,z);
-	Vec4; (x,y,z,w)
+impl_int_vec_signed!(
+	IVec2; (x,y);
+	IVec3; (x,y,z);
+	I16Vec2; (x,y);
+	I16Vec3; (x,y,z)
 );
 
 impl Vec2 {
@@ -244,4 +573,124 @@ pub fn mul_vec3d(vec:&Vec4, multiplier:f32) -> Vec4 {
 
 pub fn div_vec3d(vec:&Vec4, divider:f32) -> Vec4 {
 	return Vec4::xyz1(vec.x / divider, vec.y / divider, vec.z / divider);
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn integer_vectors() {
+		let a = IVec2::new(3, -4);
+		let b = IVec2::new(-1, 2);
+		assert_eq!(a + b * 2, IVec2::new(1, 0));
+		assert_eq!(a - b, IVec2::new(4, -6));
+		assert_eq!(a.dot(&b), -11);
+		assert_eq!((a.manhattan(&b), a.chebyshev(&b)), (10, 6));
+		assert_eq!(IVec3::new(i32::MIN, 0, 0).manhattan(&IVec3::new(i32::MAX, 0, 0)), u32::MAX);
+		assert_eq!(UVec3::new(1, 5, 2).chebyshev(&UVec3::new(4, 0, 2)), 5);
+		assert_eq!(I16Vec2::new(-7, 9) / I16Vec2::new(2, 2), I16Vec2::new(-3, 4));
+	}
+
+	#[test]
+	fn operator_matrix() {
+		let a = IVec3::new(7, -8, 9);
+		let b = IVec3::new(2, 3, -4);
+		let expected = [IVec3::new(9, -5, 5), IVec3::new(5, -11, 13), IVec3::new(14, -24, -36), IVec3::new(3, -2, -2), IVec3::new(1, -2, 1)];
+		let ops:[fn(&IVec3, &IVec3) -> [IVec3; 4]; 5] = [
+			|a, b| [*a + *b, a + b, *a + b, a + *b],
+			|a, b| [*a - *b, a - b, *a - b, a - *b],
+			|a, b| [*a * *b, a * b, *a * b, a * *b],
+			|a, b| [*a / *b, a / b, *a / b, a / *b],
+			|a, b| [*a % *b, a % b, *a % b, a % *b],
+		];
+		for (op, expected) in ops.iter().zip(expected) {
+			assert_eq!(op(&a, &b), [expected; 4]);
+		}
+		assert_eq!((a + 1, &a - 1, 2 * a, 2 * &a, a / 2, a % 2), (IVec3::new(8, -7, 10), IVec3::new(6, -9, 8),
+			IVec3::new(14, -16, 18), IVec3::new(14, -16, 18), IVec3::new(3, -4, 4), IVec3::new(1, 0, 1)));
+		assert_eq!((10 - a, 72 / &a, 10 % a), (IVec3::new(3, 18, 1), IVec3::new(10, -9, 8), IVec3::new(3, 2, 1)));
+		assert_eq!((-a, -&b), (IVec3::new(-7, 8, -9), IVec3::new(-2, -3, 4)));
+		let mut c = a;
+		c += &b;
+		c -= 1;
+		c *= b;
+		c /= 2;
+		c %= IVec3::new(5, 5, 5);
+		assert_eq!(c, IVec3::new(3, -4, -3));
+		let v = -Vec2::new(1.5, -3.0) * Vec2::new(2.0, 2.0) / 3.0 % 0.75;
+		assert_eq!((v.x, v.y), (-0.25, 0.5));
+		assert_eq!(UVec2::new(9, 4) - 4, UVec2::new(5, 0));
+	}
+
+	#[test]
+	fn component_wise() {
+		let a = Vec3::new(-1.25, 2.5, -0.0);
+		let b = Vec3::new(0.5, 3.0, -4.0);
+		let parts = |v:Vec3| (v.x.to_bits(), v.y.to_bits(), v.z.to_bits());
+		let expected = |x:f32, y:f32, z:f32| (x.to_bits(), y.to_bits(), z.to_bits());
+		assert_eq!(parts(a.min(&b)), expected(-1.25, 2.5, -4.0));
+		assert_eq!(parts(a.max(&b)), expected(0.5, 3.0, -0.0));
+		assert_eq!(parts(b.clamp(&Vec3::new(-1.0, -1.0, -1.0), &Vec3::ONE)), expected(0.5, 1.0, -1.0));
+		assert_eq!(parts(a.abs()), expected(1.25, 2.5, 0.0));
+		assert_eq!(parts(a.signum()), expected(-1.0, 1.0, -1.0));
+		assert_eq!(parts(a.floor()), expected(-2.0, 2.0, -0.0));
+		assert_eq!(parts(a.ceil()), expected(-1.0, 3.0, -0.0));
+		assert_eq!(parts(a.round()), expected(-1.0, 3.0, -0.0));
+		assert_eq!(parts(a.fract()), expected(0.75, 0.5, 0.0));
+		assert_eq!((a.min_element(), a.max_element(), b.min_element()), (-1.25, 2.5, -4.0));
+		assert_eq!(Vec2::new(f32::NAN, 1.0).min(&Vec2::ZERO).y, 0.0);
+		assert_eq!(Vec2::new(1.0, f32::NAN).max_element(), 1.0);
+		let mask = a.cmplt(&b);
+		assert_eq!(mask, BVec3::new(true, true, false));
+		assert!(mask.any() && !mask.all() && (!mask).any());
+		assert_eq!(parts(Vec3::select(&mask, &a, &b)), expected(-1.25, 2.5, -4.0));
+		let d = DVec2::new(-2.5, 1e300).floor();
+		assert_eq!((d.x, d.y), (-3.0, 1e300));
+	}
+
+	#[test]
+	fn integer_component_wise() {
+		let a = IVec3::new(-3, 7, 0);
+		let b = IVec3::new(2, -8, 0);
+		assert_eq!((a.min(&b), a.max(&b)), (IVec3::new(-3, -8, 0), IVec3::new(2, 7, 0)));
+		assert_eq!(a.clamp(&IVec3::new(-1, -1, -1), &IVec3::ONE), IVec3::new(-1, 1, 0));
+		assert_eq!((a.abs(), a.signum()), (IVec3::new(3, 7, 0), IVec3::new(-1, 1, 0)));
+		assert_eq!((b.min_element(), b.max_element()), (-8, 2));
+		assert_eq!(IVec3::select(&a.cmpge(&b), &a, &b), a.max(&b));
+		assert_eq!(UVec2::new(4, 9).cmpeq(&UVec2::new(4, 1)), BVec2::new(true, false));
+		assert_eq!(I16Vec2::new(-5, 5).abs().min_element(), 5);
+	}
+
+	#[test]
+	fn float_conversions() {
+		let v = Vec2::new(-1.5, 2.5);
+		assert_eq!(IVec2::from_floor(&v), IVec2::new(-2, 2));
+		assert_eq!(IVec2::from_round(&v), IVec2::new(-2, 3));
+		assert_eq!(IVec2::from_trunc(&v), IVec2::new(-1, 2));
+		assert_eq!(UVec2::from_floor(&v), UVec2::new(0, 2));
+		assert_eq!(I16Vec3::from_round(&Vec3::new(1e9, -1e9, f32::NAN)), I16Vec3::new(i16::MAX, i16::MIN, 0));
+		let f:Vec3 = IVec3::new(1, -2, 3).into();
+		assert_eq!((f.x, f.y, f.z), (1.0, -2.0, 3.0));
+	}
+
+	#[test]
+	fn f64_vectors() {
+		// 1e8 + 0.25 isn't representable in f32, where the spacing is 8.
+		let a = DVec3::new(1e8, 0.0, 0.0);
+		let b = a + DVec3::new(0.25, 3.0, 4.0);
+		assert!(((b - a).len() - 25.0625f64.sqrt()).abs() <= 1e-15);
+		assert_eq!((b - a).dot(&DVec3::ONE), 7.25);
+		assert_eq!(a.lerp(b, 0.5).x, 1e8 + 0.125);
+		let n = DVec2::new(3.0, 4.0).normalize2();
+		assert_eq!((n.x, n.y), (0.6, 0.8));
+		let v = Vec4::new(0.1, -2.0, 1e30, 0.5);
+		assert_eq!(DVec1::from(Vec1::new(0.1)).to_f32().x, 0.1);
+		assert_eq!(DVec1::new(-4.0).len(), 4.0);
+		let d = DVec4::from(v);
+		assert_eq!((d.x, d.z), (0.1f32 as f64, 1e30f32 as f64));
+		let back = d.to_f32();
+		assert_eq!((back.x, back.y, back.z, back.w), (v.x, v.y, v.z, v.w));
+		assert_eq!(DVec2::new(1e300, 0.0).to_f32().x, f32::INFINITY);
+		assert_eq!(Vec2::new(0.5, 1.0).lerp(Vec2::ONE, 0.5).x, 0.75);
+	}
+}